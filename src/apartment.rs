@@ -1,41 +1,109 @@
-use crate::resident::Resident;
+use crate::{mail::Mail, resident::Resident};
 use std::sync::{Arc, Mutex};
 
+#[derive(Clone)]
 pub struct Apartment {
-    pub is_opened: bool,
+    pub is_opened: bool, // false while locked until the morning
+    pub is_sealed: bool, // locked by the Janitor, which the morning doesn't undo
     pub number: usize,
     pub floor: usize,
     pub guests: Vec<usize>,
     pub resident: Option<Arc<Mutex<Resident>>>,
-    pub mails: Vec<String>,
+    pub mails: Vec<Mail>,
+    pub sent_mails: Vec<Mail>,
 }
 
 impl Apartment {
     pub fn new(number: usize, floor: usize) -> Apartment {
         Apartment {
             is_opened: true,
+            is_sealed: false,
             number,
             floor,
             guests: vec![],
             resident: None,
             mails: vec![],
+            sent_mails: vec![],
         }
     }
 
+    /// Whether only those holding its key can get in.
+    pub fn is_locked(&self) -> bool {
+        !self.is_opened || self.is_sealed
+    }
+
     pub fn assign_resident(&mut self, resident: Resident) {
         if self.is_available() {
             self.resident = Some(Arc::new(Mutex::new(resident)));
         } else {
-            println!("Apartment is already occupied.");
+            say!("{}", tr!("apartment.occupied"));
+        }
+    }
+
+    pub fn receive_mail(&mut self, mail: Mail) {
+        self.mails.push(mail);
+    }
+
+    pub fn read_mails(&mut self) {
+        for (i, mail) in self.mails.iter_mut().enumerate() {
+            if !mail.is_read {
+                say!("{}", tr!("mail.numbered", i + 1, mail));
+                mail.is_read = true;
+            }
+        }
+    }
+
+    pub fn print_inbox(&self) {
+        if self.mails.is_empty() {
+            say!("{}", tr!("mail.inbox_empty"));
+        }
+        for (i, mail) in self.mails.iter().enumerate() {
+            say!("{}", tr!("mail.numbered", i + 1, mail));
+        }
+    }
+
+    pub fn print_sent_mails(&self) {
+        if self.sent_mails.is_empty() {
+            say!("{}", tr!("mail.none_sent"));
+        }
+        for (i, mail) in self.sent_mails.iter().enumerate() {
+            let signature = match (mail.is_anonymous, mail.signed_as) {
+                (true, _) => tr!("mail.anonymous_signature"),
+                (false, Some(signed_as)) => tr!("mail.signed_as", signed_as),
+                (false, None) => String::new(),
+            };
+            say!(
+                "{}",
+                tr!(
+                    "mail.sent",
+                    i + 1,
+                    mail.day,
+                    mail.to,
+                    signature,
+                    mail.contents
+                )
+            );
         }
     }
 
-    pub fn read_mails(&self) {
-        for i in 0..self.mails.len() {
-            println!("Mail {}: {}", i + 1, self.mails[i]);
+    pub fn read_mail(&mut self, index: usize) -> Option<&Mail> {
+        let mail = self.mails.get_mut(index)?;
+        mail.is_read = true;
+        Some(mail)
+    }
+
+    pub fn delete_mail(&mut self, index: usize) -> Option<Mail> {
+        if index < self.mails.len() {
+            Some(self.mails.remove(index))
+        } else {
+            None
         }
     }
 
+    pub fn unread_mails_count(&self) -> usize {
+        self.mails.iter().filter(|mail| !mail.is_read).count()
+    }
+
     pub fn clear_mails(&mut self) {
         self.mails.clear();
     }
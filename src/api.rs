@@ -0,0 +1,818 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+use crate::{
+    bulletin_board::NoticeKind,
+    command::ArgKind,
+    configs,
+    console::{self, BufferChannel},
+    document::OLDEST_AGE,
+    mail::Mail,
+    manager::Manager,
+    manager_states::SettleResidentsState,
+    resident::{ResidentType, Status},
+    roles::Role,
+    strategies::killer_strategy::KillerAction,
+    validation::{self, Setting},
+};
+
+pub const DEFAULT_PORT: u16 = 8080;
+
+/// The largest request body read; anything longer is refused before it is read.
+const MAX_BODY: usize = 64 * 1024;
+/// The longest request line, and the most header bytes, read before the request is refused.
+const MAX_REQUEST_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 16 * 1024;
+/// How long a client may stay silent before its connection is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message }),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            403 => "Forbidden",
+            404 => "Not Found",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            414 => "URI Too Long",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+pub struct ApiServer {
+    listener: TcpListener,
+}
+
+impl ApiServer {
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        Ok(Self { listener })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves requests one at a time; the manager is the single source of truth for every client.
+    pub fn run(&self, manager: &mut Manager) -> io::Result<()> {
+        println!("{}", tr!("api.listening", self.local_addr()?));
+        for stream in self.listener.incoming() {
+            if let Err(error) = self.handle_connection(manager, stream?) {
+                println!("{}", tr!("api.connection_error", error));
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_connection(&self, manager: &mut Manager, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let response = self.read_and_route(manager, &stream)?;
+        let body = response.body.to_string();
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            response.reason(),
+            body.len(),
+            body
+        )?;
+        stream.flush()?;
+        if matches!(response.status, 413 | 414 | 431) {
+            // what the client still sends of a refused request would reset the connection
+            // before it reads the answer
+            stream.shutdown(Shutdown::Write)?;
+            let _ = io::copy(&mut stream.take(MAX_BODY as u64), &mut io::sink());
+        }
+        Ok(())
+    }
+
+    fn read_and_route(&self, manager: &mut Manager, stream: &TcpStream) -> io::Result<Response> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let Some(request_line) = read_capped_line(&mut reader, MAX_REQUEST_LINE)? else {
+            return Ok(Response::error(
+                414,
+                &tr!("api.request_line_too_long", MAX_REQUEST_LINE),
+            ));
+        };
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let path = parts.next().unwrap_or_default().to_string();
+
+        let mut content_length = 0;
+        let mut headers_left = MAX_HEADERS;
+        loop {
+            let Some(header) = read_capped_line(&mut reader, headers_left)? else {
+                return Ok(Response::error(
+                    431,
+                    &tr!("api.headers_too_large", MAX_HEADERS),
+                ));
+            };
+            headers_left -= header.len();
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        Ok(if content_length > MAX_BODY {
+            Response::error(413, &tr!("api.body_too_large", MAX_BODY))
+        } else {
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body)?;
+            match serde_json::from_slice::<Value>(&body) {
+                Ok(body) => route(manager, &method, &path, &body),
+                Err(_) if body.is_empty() => route(manager, &method, &path, &Value::Null),
+                Err(_) => Response::error(400, &tr!("api.not_json")),
+            }
+        })
+    }
+}
+
+/// Reads a line of at most `limit` bytes; `None` if it is longer. An empty string at the end
+/// of the stream.
+fn read_capped_line(reader: &mut impl BufRead, limit: usize) -> io::Result<Option<String>> {
+    let mut line = vec![];
+    reader.take(limit as u64 + 1).read_until(b'\n', &mut line)?;
+    if line.len() > limit {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+}
+
+/// Maps a request onto the manager's state machine and describes the outcome as JSON, with
+/// whatever the hotel said meanwhile as its `log`.
+pub fn route(manager: &mut Manager, method: &str, path: &str, body: &Value) -> Response {
+    let channel = BufferChannel::new(vec![]);
+    let output = channel.output();
+    let host = console::set_host(Some(Box::new(channel)));
+    let mut response = dispatch(manager, method, path, body);
+    console::set_host(host);
+    if let Some(body) = response.body.as_object_mut() {
+        let output = output.borrow();
+        if !output.is_empty() && !body.contains_key("log") {
+            body.insert(
+                "log".to_string(),
+                json!(output.lines().collect::<Vec<&str>>()),
+            );
+        }
+    }
+    response
+}
+
+fn dispatch(manager: &mut Manager, method: &str, path: &str, body: &Value) -> Response {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let credentials = Credentials::parse(query);
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["state"]) => Response::ok(state(manager)),
+        ("POST", ["hotel"]) => {
+            *manager = Manager::new();
+            Response::ok(hotel_config(manager))
+        }
+        ("GET", ["hotel", "config"]) => Response::ok(hotel_config(manager)),
+        ("PUT", ["hotel", "config"]) => configure(manager, body),
+        ("POST", ["hotel", "set"]) => transition(manager, "SetUpHotel", &["hotel", "set"]),
+        ("POST", ["residents"]) => add_resident(manager, body),
+        ("POST", ["residents", "settled"]) => {
+            transition(manager, "SettleResidents", &["residents", "settled"])
+        }
+        ("POST", ["play"]) => {
+            let response = match body.get("secret").and_then(Value::as_str) {
+                Some(secret) => transition(manager, "Game", &["play", secret]),
+                None => transition(manager, "Game", &["play"]),
+            };
+            if response.status == 200 {
+                manager.game_flow.advance_to_human();
+                return Response::ok(state(manager));
+            }
+            response
+        }
+        ("POST", ["residents", apartment, "action"]) => {
+            match credentials.admit(manager, apartment) {
+                Ok(apartment) => night_action(manager, apartment, body),
+                Err(response) => response,
+            }
+        }
+        ("GET", ["residents", apartment, "view"]) => match credentials.admit(manager, apartment) {
+            Ok(apartment) => player_view(manager, apartment),
+            Err(response) => response,
+        },
+        ("GET", ["residents", apartment, "inbox"]) => match credentials.admit(manager, apartment) {
+            Ok(apartment) => {
+                Response::ok(inbox(&manager.game_flow.hotel.apartments[apartment].mails))
+            }
+            Err(response) => response,
+        },
+        _ => Response::error(404, &tr!("api.no_endpoint")),
+    }
+}
+
+/// What a request to a resident's endpoints proves about its sender, from `?pin=` or `?secret=`.
+#[derive(Default)]
+struct Credentials<'a> {
+    pin: Option<&'a str>,
+    secret: Option<&'a str>,
+}
+
+impl<'a> Credentials<'a> {
+    fn parse(query: &'a str) -> Self {
+        let mut credentials = Self::default();
+        for (name, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            match name {
+                "pin" => credentials.pin = Some(value),
+                "secret" => credentials.secret = Some(value),
+                _ => {}
+            }
+        }
+        credentials
+    }
+
+    /// The apartment, if it has a resident and the sender is them, by their PIN, or the game
+    /// master, by the secret chosen at `play`.
+    fn admit(&self, manager: &Manager, apartment: &str) -> Result<usize, Response> {
+        let Ok(apartment) = apartment.parse::<usize>() else {
+            return Err(Response::error(400, &tr!("api.apartment_number")));
+        };
+        let hotel = &manager.game_flow.hotel;
+        let Some(resident) = hotel
+            .apartments
+            .get(apartment)
+            .and_then(|a| a.resident.as_ref())
+        else {
+            return Err(Response::error(404, &tr!("api.no_resident")));
+        };
+        let pin = resident.lock().unwrap().pin.clone();
+        let is_resident = pin.is_some() && pin.as_deref() == self.pin;
+        let is_game_master =
+            self.secret.is_some() && manager.game_flow.game_master_secret() == self.secret;
+        if !is_resident && !is_game_master {
+            return Err(Response::error(403, &tr!("api.credentials")));
+        }
+        Ok(apartment)
+    }
+}
+
+fn state(manager: &Manager) -> Value {
+    let game_flow = &manager.game_flow;
+    let current_player = game_flow
+        .residents
+        .get(game_flow.current_moving_player)
+        .map(|resident| resident.lock().unwrap().apartment_number);
+    json!({
+        "state": manager.state().name(),
+        "hotel_id": game_flow.hotel.id,
+        "day": game_flow.game_history.day,
+        "time": format!("{:?}", game_flow.current_state),
+        "current_player": current_player,
+        "human_turn": game_flow.is_human_turn(),
+    })
+}
+
+fn hotel_config(manager: &Manager) -> Value {
+    let hotel = &manager.game_flow.hotel;
+    json!({
+        "id": hotel.id,
+        "num_rooms": hotel.num_rooms,
+        "rooms_per_story": hotel.rooms_per_story,
+        "elevator_position": hotel.elevator_position,
+        "building_type": hotel.building_type.name(),
+        "capital": hotel.capital,
+        "entrance_fee": hotel.entrance_fee,
+        "daily_costs": hotel.daily_costs,
+    })
+}
+
+fn transition(manager: &mut Manager, expected_state: &str, command: &[&str]) -> Response {
+    if manager.state().name() != expected_state {
+        return Response::error(
+            409,
+            &tr!("api.wrong_state", expected_state, manager.state().name()),
+        );
+    }
+    manager.handle_command(command);
+    if manager.state().name() == expected_state {
+        return Response::error(400, &tr!("api.not_ready"));
+    }
+    Response::ok(state(manager))
+}
+
+/// Applies every field of `body` to the hotel, or none of them if any is wrong.
+fn configure(manager: &mut Manager, body: &Value) -> Response {
+    if manager.state().name() != "SetUpHotel" {
+        return Response::error(409, &tr!("api.setup_only"));
+    }
+    let mut hotel = manager.game_flow.hotel.clone();
+    let mut problems = vec![];
+    match body.get("id") {
+        None => {}
+        Some(Value::String(id)) => match configs::load(id) {
+            Ok(Some(saved)) => hotel = saved,
+            Ok(None) => hotel.id = id.clone(),
+            Err(error) => problems.push(error),
+        },
+        Some(_) => problems.push(tr!("api.invalid_id")),
+    }
+    let fields = [
+        ("num_rooms", Setting::Rooms),
+        ("rooms_per_story", Setting::RoomsPerStory),
+        ("elevator_position", Setting::Elevator),
+        ("building_type", Setting::Building),
+        ("capital", Setting::Capital),
+        ("entrance_fee", Setting::Fee),
+        ("daily_costs", Setting::Service),
+    ];
+    for (field, setting) in fields {
+        let kind = setting.kind();
+        // names are given as strings and amounts as numbers, never the other way round
+        let token = match (body.get(field), kind) {
+            (None, _) => continue,
+            (Some(Value::String(name)), ArgKind::Choice(_)) => Some(name.clone()),
+            (Some(Value::Number(number)), kind) if !matches!(kind, ArgKind::Choice(_)) => {
+                Some(number.to_string())
+            }
+            (Some(_), _) => None,
+        };
+        let Some(value) = token.and_then(|token| kind.parse(&token)) else {
+            problems.push(tr!("api.invalid_field", field, kind.expected()));
+            continue;
+        };
+        if let Err(error) = validation::set(&mut hotel, setting, &value) {
+            problems.push(error);
+        }
+    }
+    if !problems.is_empty() {
+        return Response {
+            status: 400,
+            body: json!({ "error": tr!("api.not_changed"), "problems": problems }),
+        };
+    }
+    manager.game_flow.hotel = hotel;
+    Response::ok(hotel_config(manager))
+}
+
+fn add_resident(manager: &mut Manager, body: &Value) -> Response {
+    if manager.state().name() != "SettleResidents" {
+        return Response::error(409, &tr!("api.settlement_only"));
+    }
+    let name = body.get("name").and_then(Value::as_str);
+    let age = body
+        .get("age")
+        .and_then(Value::as_u64)
+        .filter(|&age| (1..=OLDEST_AGE as u64).contains(&age));
+    let account_balance = body.get("account_balance").and_then(Value::as_f64);
+    let apartment = body.get("apartment").and_then(Value::as_u64);
+    let pin = body.get("pin").and_then(Value::as_str).map(str::to_string);
+    let (Some(name), Some(age), Some(account_balance), Some(apartment)) =
+        (name, age, account_balance, apartment)
+    else {
+        return Response::error(400, &tr!("api.resident_fields", OLDEST_AGE));
+    };
+
+    let apartment = apartment as usize;
+    let hotel = &mut manager.game_flow.hotel;
+    SettleResidentsState.add_resident(
+        hotel,
+        name.to_string(),
+        age as usize,
+        account_balance,
+        Some(apartment),
+        pin,
+        ResidentType::Human,
+    );
+    match hotel
+        .apartments
+        .get(apartment)
+        .and_then(|a| a.resident.as_ref())
+    {
+        Some(resident) if resident.lock().unwrap().name == name => Response::ok(json!({
+            "apartment": apartment,
+            "name": name,
+        })),
+        _ => Response::error(409, &tr!("api.not_settled")),
+    }
+}
+
+fn night_action(manager: &mut Manager, apartment: usize, body: &Value) -> Response {
+    if manager.state().name() != "Playing" {
+        return Response::error(409, &tr!("api.not_playing"));
+    }
+    let game_flow = &mut manager.game_flow;
+    let current = game_flow
+        .residents
+        .get(game_flow.current_moving_player)
+        .map(|resident| resident.lock().unwrap().apartment_number);
+    if current != Some(apartment) || !game_flow.is_human_turn() {
+        return Response::error(409, &tr!("api.not_their_turn"));
+    }
+
+    let (role, keys) = {
+        let resident = game_flow.residents[game_flow.current_moving_player]
+            .lock()
+            .unwrap();
+        (resident.strategy.confess_role(), resident.keys.clone())
+    };
+    let mut inputs = vec![];
+    if role != Role::Judge {
+        let ready = game_flow.hotel.get_ready_apartments(Some(apartment), &keys);
+        match body.get("target").and_then(Value::as_u64) {
+            Some(target) if ready.contains(&(target as usize)) => inputs.push(target.to_string()),
+            _ => {
+                return Response::error(
+                    400,
+                    &tr!(
+                        "api.target",
+                        ready
+                            .iter()
+                            .map(usize::to_string)
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                )
+            }
+        }
+        let action = body.get("action").and_then(Value::as_u64);
+        let target = inputs[0].parse().unwrap();
+        let valid_action = match (role, action) {
+            (Role::Killer, Some(action)) => {
+                (1..=KillerAction::iter().count() as u64).contains(&action)
+            }
+            (Role::Avenger, Some(1)) => true,
+            (Role::Avenger, Some(2)) => game_flow.game_history.has_visited(apartment, target),
+            (Role::Killer | Role::Avenger, None) => false,
+            (_, _) => true,
+        };
+        if !valid_action {
+            return Response::error(400, &tr!("api.action"));
+        }
+        inputs.extend(action.map(|action| action.to_string()));
+    }
+    if let Some(answers) = body.get("answers").and_then(Value::as_array) {
+        inputs.extend(answers.iter().map(|answer| match answer {
+            Value::String(answer) => answer.clone(),
+            answer => answer.to_string(),
+        }));
+    }
+
+    let channel = BufferChannel::new(inputs);
+    let output = channel.output();
+    console::register(apartment, Box::new(channel));
+    game_flow.next_turn();
+    console::unregister(apartment);
+    game_flow.advance_to_human();
+
+    let mut response = state(manager);
+    response["log"] = json!(output.borrow().lines().collect::<Vec<&str>>());
+    Response::ok(response)
+}
+
+fn player_view(manager: &Manager, apartment: usize) -> Response {
+    let mut view = state(manager);
+    let hotel = &manager.game_flow.hotel;
+    let Some(resident) = hotel
+        .apartments
+        .get(apartment)
+        .and_then(|a| a.resident.as_ref())
+    else {
+        return Response::error(404, &tr!("api.no_resident"));
+    };
+    let resident = resident.lock().unwrap();
+    let documents: Vec<Value> = resident
+        .documents
+        .iter()
+        .map(|document| {
+            json!({
+                "role": format!("{:?}", document.role),
+                "name": document.name,
+                "year_of_birth": document.year_of_birth,
+                "title": document.title,
+                "accomplishments": document.accomplishments,
+                "number": document.number,
+                "issued": document.issued.to_string(),
+            })
+        })
+        .collect();
+    let apartments: Vec<Value> = hotel
+        .apartments
+        .iter()
+        .map(|apt| {
+            let name = apt.resident.as_ref().map(|neighbour| {
+                if apt.number == apartment {
+                    resident.name.clone()
+                } else {
+                    neighbour.lock().unwrap().name.clone()
+                }
+            });
+            json!({
+                "number": apt.number,
+                "floor": apt.floor,
+                "resident": name,
+                "reported_dead": hotel.bulletin_board.has_notice(NoticeKind::Death, apt.number),
+            })
+        })
+        .collect();
+
+    view["you"] = json!({
+        "apartment": apartment,
+        "name": resident.name,
+        "age": resident.age,
+        "role": format!("{:?}", resident.strategy.confess_role()),
+        "human": resident.resident_type == ResidentType::Human,
+        "alive": resident.status == Status::Alive,
+        "super_status": format!("{:?}", resident.super_status),
+        "account_balance": resident.account_balance,
+        "documents": documents,
+    });
+    view["inbox"] = inbox(&hotel.apartments[apartment].mails);
+    view["board"] = json!({
+        "pinned": hotel.bulletin_board.pinned,
+        "notices": hotel.bulletin_board.notices,
+    });
+    view["hotel"] = json!({
+        "rooms_per_story": hotel.rooms_per_story,
+        "apartments": apartments,
+    });
+    Response::ok(view)
+}
+
+fn inbox(mails: &[Mail]) -> Value {
+    json!(mails
+        .iter()
+        .enumerate()
+        .map(|(i, mail)| json!({
+            "number": i + 1,
+            "day": mail.day,
+            "from": mail.shown_sender(),
+            "contents": mail.contents,
+            "is_read": mail.is_read,
+        }))
+        .collect::<Vec<Value>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::*;
+    use crate::strategies::{killer_strategy::KillerStrategy, police_strategy::PoliceStrategy};
+
+    fn request(manager: &mut Manager, method: &str, path: &str, body: Value) -> Response {
+        route(manager, method, path, &body)
+    }
+
+    fn settled_manager() -> Manager {
+        let mut manager = Manager::new();
+        request(&mut manager, "POST", "/hotel", Value::Null);
+        request(
+            &mut manager,
+            "PUT",
+            "/hotel/config",
+            json!({ "id": "api_test", "num_rooms": 2 }),
+        );
+        let response = request(&mut manager, "POST", "/hotel/set", Value::Null);
+        assert!(response.body["log"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|line| line == "Hotel setup complete. Moving to resident settlement stage."));
+        let response = request(
+            &mut manager,
+            "POST",
+            "/residents",
+            json!({ "name": "Alice Johnson", "age": 30, "account_balance": 1000.0, "apartment": 0, "pin": "1234" }),
+        );
+        assert_eq!(response.status, 200);
+        let response = request(
+            &mut manager,
+            "POST",
+            "/residents",
+            json!({ "name": "Bob", "age": 30, "account_balance": 10.0, "apartment": 0 }),
+        );
+        assert_eq!(response.status, 409);
+        assert_eq!(response.body["log"], json!(["Room unavailable"]));
+        request(&mut manager, "POST", "/residents/settled", Value::Null);
+
+        let hotel = &manager.game_flow.hotel;
+        hotel.apartments[0]
+            .resident
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .strategy = Arc::new(KillerStrategy);
+        hotel.apartments[1]
+            .resident
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .strategy = Arc::new(PoliceStrategy);
+        manager
+    }
+
+    #[test]
+    fn test_requests_follow_the_state_machine() {
+        let mut manager = Manager::new();
+
+        let response = request(&mut manager, "POST", "/play", Value::Null);
+        assert_eq!(response.status, 409);
+
+        let response = request(
+            &mut manager,
+            "PUT",
+            "/hotel/config",
+            json!({ "num_rooms": "many" }),
+        );
+        assert_eq!(response.status, 400);
+
+        let rooms = manager.game_flow.hotel.num_rooms;
+        let response = request(
+            &mut manager,
+            "PUT",
+            "/hotel/config",
+            json!({ "num_rooms": 20, "capital": 0, "rooms_per_story": -1 }),
+        );
+        assert_eq!(response.status, 400);
+        assert_eq!(response.body["problems"].as_array().unwrap().len(), 2);
+        assert_eq!(manager.game_flow.hotel.num_rooms, rooms);
+
+        let response = request(&mut manager, "GET", "/nowhere", Value::Null);
+        assert_eq!(response.status, 404);
+    }
+
+    #[test]
+    fn test_night_action_and_player_view() {
+        let mut manager = settled_manager();
+        let response = request(&mut manager, "POST", "/play", json!({ "secret": "gm" }));
+        assert_eq!(response.body["state"], "Playing");
+        assert_eq!(response.body["current_player"], 0);
+
+        let response = request(&mut manager, "POST", "/residents/1/action", json!({}));
+        assert_eq!(response.status, 403);
+        let response = request(
+            &mut manager,
+            "POST",
+            "/residents/1/action?secret=gm",
+            json!({}),
+        );
+        assert_eq!(response.status, 409);
+        let response = request(
+            &mut manager,
+            "POST",
+            "/residents/0/action?pin=1234",
+            json!({ "target": 0, "action": 1 }),
+        );
+        assert_eq!(response.status, 400);
+
+        let response = request(
+            &mut manager,
+            "POST",
+            "/residents/0/action?pin=1234",
+            json!({ "target": 1, "action": 1 }),
+        );
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["human_turn"], true);
+        assert!(response.body["log"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|line| line == "Killer kills the resident in apartment 1"));
+
+        for path in [
+            "/residents/0/view",
+            "/residents/0/view?pin=4321",
+            "/residents/0/inbox?secret=",
+            "/residents/1/inbox?pin=",
+        ] {
+            assert_eq!(request(&mut manager, "GET", path, Value::Null).status, 403);
+        }
+        let response = request(
+            &mut manager,
+            "GET",
+            "/residents/1/inbox?secret=gm",
+            Value::Null,
+        );
+        assert_eq!(response.status, 200);
+        let view = request(
+            &mut manager,
+            "GET",
+            "/residents/0/view?pin=1234",
+            Value::Null,
+        )
+        .body;
+        assert_eq!(view["you"]["name"], "Alice Johnson");
+        assert_eq!(view["you"]["role"], "Killer");
+        assert_eq!(view["hotel"]["apartments"][1]["reported_dead"], true);
+        assert!(view["hotel"]["apartments"][1].get("role").is_none());
+    }
+
+    #[test]
+    fn test_http_roundtrip() {
+        let mut manager = Manager::new();
+        let server = ApiServer::bind(0).unwrap();
+        let address = server.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let body = r#"{"num_rooms": 12}"#;
+            write!(
+                stream,
+                "PUT /hotel/config HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let (stream, _) = server.listener.accept().unwrap();
+        server.handle_connection(&mut manager, stream).unwrap();
+        let response = client.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(r#""num_rooms":12"#));
+        assert_eq!(manager.game_flow.hotel.num_rooms, 12);
+    }
+
+    #[test]
+    fn test_oversized_body_is_refused_unread() {
+        let mut manager = Manager::new();
+        let server = ApiServer::bind(0).unwrap();
+        let address = server.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "PUT /hotel/config HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n"
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let (stream, _) = server.listener.accept().unwrap();
+        server.handle_connection(&mut manager, stream).unwrap();
+        let response = client.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"));
+    }
+
+    #[test]
+    fn test_oversized_request_line_and_headers_are_refused() {
+        let long_path = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_REQUEST_LINE));
+        let long_headers = format!(
+            "GET /state HTTP/1.1\r\n{}\r\n",
+            "X-Padding: aaaaaaaaaaaaaaaa\r\n".repeat(MAX_HEADERS / 16)
+        );
+        for (request, status) in [
+            (long_path, "414 URI Too Long"),
+            (long_headers, "431 Request Header Fields Too Large"),
+        ] {
+            let mut manager = Manager::new();
+            let server = ApiServer::bind(0).unwrap();
+            let address = server.local_addr().unwrap();
+            let client = thread::spawn(move || {
+                let mut stream = TcpStream::connect(address).unwrap();
+                stream.write_all(request.as_bytes()).unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).unwrap();
+                response
+            });
+
+            let (stream, _) = server.listener.accept().unwrap();
+            server.handle_connection(&mut manager, stream).unwrap();
+            let response = client.join().unwrap();
+
+            assert!(response.starts_with(&format!("HTTP/1.1 {}", status)));
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::locale;
+
+#[derive(EnumIter, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NoticeKind {
+    Announcement,
+    Death,
+    Arrest,
+}
+
+impl NoticeKind {
+    pub fn parse(kind: &str) -> Option<Self> {
+        NoticeKind::iter().find(|k| {
+            let name = format!("{:?}", k).to_lowercase();
+            kind == name || kind == format!("{}s", name)
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Notice {
+    pub day: usize,
+    pub kind: NoticeKind,
+    pub author: Option<usize>, // apartment of the author, `None` for anonymous and system notices
+    pub subject: Option<usize>, // apartment the notice is about
+    pub text: String,
+}
+
+impl fmt::Display for Notice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tag = match (self.kind, self.author) {
+            (NoticeKind::Announcement, Some(author)) => tr!("mail.apartment", author),
+            (NoticeKind::Announcement, None) => tr!("mail.anonymous"),
+            (kind, _) => tr!(&format!("board.{:?}", kind)),
+        };
+        write!(f, "[{}] {}", tag, self.text)
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BulletinBoard {
+    pub pinned: Vec<String>,
+    pub notices: Vec<Notice>,
+    #[serde(skip)]
+    pub viewed_day: usize,
+}
+
+impl BulletinBoard {
+    pub fn new() -> Self {
+        Self {
+            pinned: locale::list("board.rules"),
+            notices: vec![],
+            viewed_day: 1,
+        }
+    }
+
+    pub fn pin(&mut self, rule: String) {
+        self.pinned.push(rule.trim().to_string());
+    }
+
+    pub fn unpin(&mut self, index: usize) -> Option<String> {
+        if index < self.pinned.len() {
+            Some(self.pinned.remove(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn announce(&mut self, day: usize, author: Option<usize>, text: String) {
+        self.notices.push(Notice {
+            day,
+            kind: NoticeKind::Announcement,
+            author,
+            subject: None,
+            text: text.trim().to_string(),
+        });
+    }
+
+    pub fn notify(&mut self, day: usize, kind: NoticeKind, subject: usize, text: String) {
+        self.notices.push(Notice {
+            day,
+            kind,
+            author: None,
+            subject: Some(subject),
+            text,
+        });
+    }
+
+    pub fn has_notice(&self, kind: NoticeKind, subject: usize) -> bool {
+        self.notices
+            .iter()
+            .any(|notice| notice.kind == kind && notice.subject == Some(subject))
+    }
+
+    pub fn notices_of_day(&self, day: usize) -> Vec<&Notice> {
+        self.notices
+            .iter()
+            .filter(|notice| notice.day == day)
+            .collect()
+    }
+
+    pub fn notices_of_kind(&self, kind: NoticeKind) -> Vec<&Notice> {
+        self.notices
+            .iter()
+            .filter(|notice| notice.kind == kind)
+            .collect()
+    }
+
+    pub fn render_day(&self, day: usize) -> String {
+        let mut output = format!("{:=^50}\n", tr!("board.title", day));
+        for rule in &self.pinned {
+            output.push_str(&format!("📌 {}\n", rule));
+        }
+        output.push_str(&format!("{:-^50}\n", ""));
+        let notices = self.notices_of_day(day);
+        if notices.is_empty() {
+            output.push_str(&format!("{}\n", tr!("board.empty")));
+        }
+        for notice in notices {
+            output.push_str(&format!("{}\n", notice));
+        }
+        output
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use strum::{IntoEnumIterator, VariantNames};
+
+use crate::{
+    command::{Arg, ArgKind, Command, CommandRegistry, CommandSpec},
+    document::{Document, Face},
+    game_flow::GameFlow,
+    game_history::GAME_MASTER,
+    resident::{Resident, ResidentFactory, Status, SuperStatus},
+    roles::Role,
+};
+
+const CHEATS: CommandRegistry = CommandRegistry::new(&[
+    CommandSpec {
+        name: "status",
+        args: &[
+            Arg::required("apartment", ArgKind::Index),
+            Arg::required("status", ArgKind::Choice(Status::VARIANTS)),
+        ],
+        about: "about.cheat.status",
+    },
+    CommandSpec {
+        name: "superstatus",
+        args: &[
+            Arg::required("apartment", ArgKind::Index),
+            Arg::required("super_status", ArgKind::Choice(SuperStatus::VARIANTS)),
+        ],
+        about: "about.cheat.superstatus",
+    },
+    CommandSpec {
+        name: "role",
+        args: &[
+            Arg::required("apartment", ArgKind::Index),
+            Arg::required("role", ArgKind::Choice(Role::VARIANTS)),
+        ],
+        about: "about.cheat.role",
+    },
+    CommandSpec {
+        name: "give",
+        args: &[
+            Arg::required("apartment", ArgKind::Index),
+            Arg::required("amount", ArgKind::Number),
+        ],
+        about: "about.cheat.give",
+    },
+    CommandSpec {
+        name: "take",
+        args: &[
+            Arg::required("apartment", ArgKind::Index),
+            Arg::required("amount", ArgKind::Number),
+        ],
+        about: "about.cheat.take",
+    },
+    CommandSpec {
+        name: "document",
+        args: &[
+            Arg::required("apartment", ArgKind::Index),
+            Arg::required("role", ArgKind::Choice(Role::VARIANTS)),
+            Arg::required("name", ArgKind::Text),
+            Arg::optional("age", ArgKind::Age),
+        ],
+        about: "about.cheat.document",
+    },
+    CommandSpec {
+        name: "lock",
+        args: &[Arg::required("apartment", ArgKind::Index)],
+        about: "about.cheat.lock",
+    },
+    CommandSpec {
+        name: "unlock",
+        args: &[Arg::required("apartment", ArgKind::Index)],
+        about: "about.cheat.unlock",
+    },
+    CommandSpec {
+        name: "next",
+        args: &[Arg::required("apartment", ArgKind::Index)],
+        about: "about.cheat.next",
+    },
+    CommandSpec {
+        name: "revive",
+        args: &[Arg::required("apartment", ArgKind::Index)],
+        about: "about.cheat.revive",
+    },
+]);
+
+/// The variant of `T` named `name`, as the cheats' choices list them.
+fn variant<T: IntoEnumIterator + fmt::Debug>(name: &str) -> Result<T, String> {
+    T::iter()
+        .find(|variant| format!("{:?}", variant) == name)
+        .ok_or(tr!("cheat.unknown", name))
+}
+
+fn resident(game_flow: &GameFlow, apartment: usize) -> Result<Arc<Mutex<Resident>>, String> {
+    game_flow
+        .hotel
+        .apartments
+        .get(apartment)
+        .and_then(|apartment| apartment.resident.clone())
+        .ok_or(tr!("cheat.nobody", apartment))
+}
+
+/// Runs `cheat [secret] [tool] [apartment] ...` for the game master, and writes what was done
+/// into the history, e.g. "The game master revives Dan (Doctor)". A game started without a
+/// secret takes the cheats without one.
+pub fn run(game_flow: &mut GameFlow, input: &[&str]) -> Result<String, String> {
+    let input = match game_flow.game_master_secret() {
+        None => input,
+        Some(_) => {
+            let Some((secret, input)) = input.split_first() else {
+                return Ok(CHEATS.help(None));
+            };
+            game_flow.check_game_master(Some(secret))?;
+            input
+        }
+    };
+    if input.is_empty() {
+        return Ok(CHEATS.help(None));
+    }
+    let command = CHEATS.parse(input).map_err(|error| error.to_string())?;
+    let apartment = command.integer("apartment").unwrap();
+    let done = apply(game_flow, &command, apartment)?;
+    game_flow
+        .game_history
+        .add_action(GAME_MASTER, done.clone(), apartment, None);
+    Ok(tr!("cheat.done", done, apartment))
+}
+
+/// Makes the change and returns it as it reads in the history.
+fn apply(game_flow: &mut GameFlow, command: &Command, apartment: usize) -> Result<String, String> {
+    match command.name {
+        "lock" | "unlock" => {
+            let Some(target) = game_flow.hotel.apartments.get_mut(apartment) else {
+                return Err(tr!("cheat.no_apartment", apartment));
+            };
+            target.is_opened = command.name == "unlock";
+            if command.name == "unlock" {
+                target.is_sealed = false;
+            }
+            return Ok(tr!(&format!("cheat.verb.{}", command.name)));
+        }
+        "next" => {
+            let resident = resident(game_flow, apartment)?;
+            let index = game_flow
+                .residents
+                .iter()
+                .position(|other| Arc::ptr_eq(other, &resident))
+                .ok_or(tr!("cheat.no_turns", apartment))?;
+            game_flow.current_moving_player = index;
+            return Ok(tr!("cheat.verb.next"));
+        }
+        _ => {}
+    }
+
+    let resident = resident(game_flow, apartment)?;
+    let mut resident = resident.lock().unwrap();
+    let done = match command.name {
+        "status" => {
+            let status = command.text("status").unwrap();
+            resident.status = variant(status)?;
+            tr!("cheat.verb.status", status)
+        }
+        "superstatus" => {
+            let name = command.text("super_status").unwrap();
+            resident.super_status = variant(name)?;
+            tr!("cheat.verb.superstatus", name)
+        }
+        "role" => {
+            let role: Role = variant(command.text("role").unwrap())?;
+            resident.strategy = ResidentFactory::strategy(role);
+            tr!("cheat.verb.role", role)
+        }
+        "give" => {
+            let amount = command.number("amount").unwrap();
+            resident.account_balance += amount;
+            tr!("cheat.verb.give", format!("{:.2}", amount))
+        }
+        "take" => {
+            let amount = command
+                .number("amount")
+                .unwrap()
+                .min(resident.account_balance);
+            resident.account_balance -= amount;
+            tr!("cheat.verb.take", format!("{:.2}", amount))
+        }
+        "document" => {
+            let role: Role = variant(command.text("role").unwrap())?;
+            let name = command.text("name").unwrap().to_string();
+            let age = command.integer("age").unwrap_or(resident.age);
+            resident
+                .documents
+                .push(Document::new(role, name.clone(), age, Face::random()));
+            tr!("cheat.verb.document", role, name)
+        }
+        "revive" => {
+            if resident.status == Status::Alive {
+                return Err(tr!("cheat.alive", resident.name));
+            }
+            resident.status = Status::Alive;
+            resident.super_status = SuperStatus::None;
+            tr!("cheat.verb.revive")
+        }
+        _ => unreachable!("every cheat is handled"),
+    };
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game_flow::GameTime, manager::Manager};
+
+    /// Cheats with the game master's secret, given at `play`.
+    fn cheat(manager: &mut Manager, line: &str) -> Result<String, String> {
+        let line = format!("s3cret {}", line);
+        let input: Vec<&str> = line.split_whitespace().collect();
+        run(&mut manager.game_flow, &input)
+    }
+
+    fn playing() -> Manager {
+        let mut manager = Manager::new();
+        for command in ["hotel set", "residents settled", "play s3cret"] {
+            let input: Vec<&str> = command.split_whitespace().collect();
+            manager.handle_command(&input);
+        }
+        manager
+    }
+
+    #[test]
+    fn test_cheats_need_the_game_master_and_are_logged() {
+        let mut manager = playing();
+        assert_eq!(
+            run(&mut manager.game_flow, &["guess", "give", "1", "100"]),
+            Err("That is not the game master's secret.".to_string())
+        );
+        let before = resident(&manager.game_flow, 1)
+            .unwrap()
+            .lock()
+            .unwrap()
+            .clone();
+
+        for line in [
+            "give 1 100",
+            "take 1 30.5",
+            "status 1 dead",
+            "revive 1",
+            "superstatus 1 wounded",
+            "role 1 judge",
+            "document 1 police Pat 40",
+            "lock 2",
+        ] {
+            cheat(&mut manager, line).unwrap();
+        }
+
+        let game_flow = &manager.game_flow;
+        let after = resident(game_flow, 1).unwrap().lock().unwrap().clone();
+        assert!((after.account_balance - before.account_balance - 69.5).abs() < 1e-9);
+        assert_eq!(after.status, Status::Alive);
+        assert_eq!(after.super_status, SuperStatus::Wounded);
+        assert_eq!(after.strategy.confess_role(), Role::Judge);
+        let document = after.documents.last().unwrap();
+        assert_eq!(
+            (document.role, document.name.as_str()),
+            (Role::Police, "Pat")
+        );
+        assert!(!game_flow.hotel.apartments[2].is_opened);
+        let history = game_flow
+            .game_history
+            .retell_all_history(&game_flow.hotel, Some("n"));
+        assert_eq!(history.lines().count(), 8);
+        assert!(history.contains(&format!("The game master revives {}", after.name)));
+    }
+
+    #[test]
+    fn test_next_keeps_day_and_night_in_step() {
+        let mut manager = playing();
+        let apartment = |manager: &Manager, index: usize| {
+            manager.game_flow.residents[index]
+                .lock()
+                .unwrap()
+                .apartment_number
+        };
+
+        // by day, whoever is made to move next opens the night
+        let line = format!("next {}", apartment(&manager, 1));
+        cheat(&mut manager, &line).unwrap();
+        manager.game_flow.next_turn();
+        assert!(matches!(manager.game_flow.current_state, GameTime::Night));
+        assert_eq!(manager.game_flow.current_moving_player, 2);
+
+        // in the night, sending the first mover again doesn't bring the morning
+        let line = format!("next {}", apartment(&manager, 0));
+        cheat(&mut manager, &line).unwrap();
+        manager.game_flow.next_turn();
+        assert!(matches!(manager.game_flow.current_state, GameTime::Night));
+        assert_eq!(manager.game_flow.current_moving_player, 1);
+        assert_eq!(manager.game_flow.days_passed, 0);
+    }
+
+    #[test]
+    fn test_next_and_errors() {
+        let mut manager = playing();
+        let last = manager.game_flow.residents.len() - 1;
+        let apartment = manager.game_flow.residents[last]
+            .lock()
+            .unwrap()
+            .apartment_number;
+
+        cheat(&mut manager, &format!("next {}", apartment)).unwrap();
+
+        assert_eq!(manager.game_flow.current_moving_player, last);
+        assert_eq!(
+            cheat(&mut manager, "revive 0"),
+            Err(format!(
+                "{} is alive already.",
+                manager.game_flow.hotel.apartments[0]
+                    .resident
+                    .as_ref()
+                    .unwrap()
+                    .lock()
+                    .unwrap()
+                    .name
+            ))
+        );
+        assert_eq!(
+            cheat(&mut manager, "give 99 5"),
+            Err("Nobody lives in apartment 99.".to_string())
+        );
+        assert!(cheat(&mut manager, "")
+            .unwrap()
+            .contains("revive [apartment]"));
+        assert!(cheat(&mut manager, "superstatus 1 Famous").is_err());
+        assert_eq!(manager.game_flow.game_history.actions.len(), 1);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{configs, manager::Manager, random, script};
+
+/// How to start the game from the shell.
+pub fn usage() -> String {
+    tr!("cli.usage")
+}
+
+/// The stages `--state` can skip to, each with the commands that lead there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    Settle,
+    Game,
+    Play,
+}
+
+impl Stage {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "settle" => Some(Stage::Settle),
+            "game" => Some(Stage::Game),
+            "play" => Some(Stage::Play),
+            _ => None,
+        }
+    }
+
+    fn commands(self) -> &'static [&'static [&'static str]] {
+        match self {
+            Stage::Settle => &[&["hotel", "set"]],
+            Stage::Game => &[&["hotel", "set"], &["residents", "settled"]],
+            Stage::Play => &[&["hotel", "set"], &["residents", "settled"], &["play"]],
+        }
+    }
+}
+
+/// How the game was started from the shell, e.g. `Hotel --seed 7 --state play --script night.txt`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    pub load: Option<String>,
+    pub state: Option<Stage>,
+    pub script: Option<String>,
+    pub interactive: bool,
+    pub seed: Option<u64>,
+    pub bots_only: bool,
+    pub help: bool,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.iter().peekable();
+        while let Some(arg) = args.next() {
+            let mut value =
+                |what: &str| args.next().cloned().ok_or(tr!("cli.needs", arg, tr!(what)));
+            match arg.as_str() {
+                "--load" => options.load = Some(value("cli.an_id")?),
+                "--script" => options.script = Some(value("cli.a_file")?),
+                "--seed" => {
+                    let seed = value("cli.a_number")?;
+                    let seed = seed.parse().map_err(|_| tr!("cli.bad_seed", seed))?;
+                    options.seed = Some(seed);
+                }
+                "--state" => {
+                    let stage = match args.next_if(|next| !next.starts_with("--")) {
+                        Some(name) => Stage::parse(name).ok_or(tr!("cli.bad_state", name))?,
+                        None => Stage::Settle,
+                    };
+                    options.state = Some(stage);
+                }
+                "--interactive" => options.interactive = true,
+                "--bots-only" => options.bots_only = true,
+                "--help" | "-h" => options.help = true,
+                other => return Err(tr!("cli.unknown_option", other)),
+            }
+        }
+        Ok(options)
+    }
+
+    /// A manager set up as asked, or why it couldn't be.
+    pub fn start(&self) -> Result<Manager, String> {
+        if let Some(seed) = self.seed {
+            random::seed(seed);
+        }
+        let mut manager = Manager::new();
+        manager.game_flow.bots_only = self.bots_only;
+        if let Some(id) = &self.load {
+            match configs::load(id)? {
+                Some(hotel) => manager.game_flow.hotel = hotel,
+                None => return Err(tr!("configs.not_found", id)),
+            }
+        }
+        for command in self.state.map(Stage::commands).unwrap_or_default() {
+            manager.handle_command(command);
+        }
+        Ok(manager)
+    }
+
+    /// Runs the script, if there is one; `false` if it stopped on a failed expectation.
+    pub fn run_script(&self, manager: &mut Manager) -> bool {
+        let Some(path) = &self.script else {
+            return true;
+        };
+        match script::run_file(manager, path) {
+            Ok(()) => {
+                println!("{}", tr!("script.finished", path));
+                true
+            }
+            Err(error) => {
+                println!("{}", tr!("script.stopped", path, error));
+                false
+            }
+        }
+    }
+
+    /// Whether the prompt opens after everything asked for on the command line.
+    pub fn stays_interactive(&self) -> bool {
+        self.script.is_none() || self.interactive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_options_are_parsed() {
+        let options = Options::parse(&args(
+            "--seed 42 --state --bots-only --script a.txt --interactive",
+        ))
+        .unwrap();
+
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.state, Some(Stage::Settle));
+        assert!(options.bots_only && options.stays_interactive());
+        assert_eq!(options.script.as_deref(), Some("a.txt"));
+        assert!(!Options::parse(&args("--script a.txt"))
+            .unwrap()
+            .stays_interactive());
+        assert!(Options::parse(&args("--seed many")).is_err());
+        assert!(Options::parse(&args("--state attic")).is_err());
+        assert!(Options::parse(&args("--load")).is_err());
+        assert_eq!(
+            Options::parse(&args("--fly")),
+            Err("unknown option '--fly'".to_string())
+        );
+        assert_eq!(
+            Options::parse(&args("--script")),
+            Err("--script needs a file".to_string())
+        );
+        assert!(usage().contains("--bots-only"));
+    }
+
+    #[test]
+    fn test_seeded_bots_only_games_repeat() {
+        let names = || {
+            let options = Options::parse(&args("--seed 5 --bots-only --state play")).unwrap();
+            let manager = options.start().unwrap();
+            assert_eq!(manager.state().name(), "Playing");
+            let residents = manager.game_flow.hotel.get_all_residents();
+            let residents = residents.iter().map(|resident| resident.lock().unwrap());
+            residents
+                .map(|resident| (resident.name.clone(), resident.apartment_number))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(), names());
+    }
+}
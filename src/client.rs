@@ -0,0 +1,35 @@
+use std::io::{self, BufRead, Read, Write};
+use std::net::TcpStream;
+use std::thread;
+
+/// Connects a player's terminal to a hotel server: lines typed here go to the server,
+/// everything the server sends is printed as it arrives.
+pub fn connect(address: &str) -> io::Result<()> {
+    let stream = TcpStream::connect(address)?;
+    let mut reader = stream.try_clone()?;
+    let mut writer = stream;
+
+    let disconnected = tr!("client.disconnected");
+    thread::spawn(move || {
+        let mut buffer = [0; 1024];
+        let mut stdout = io::stdout();
+        while let Ok(read) = reader.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            stdout.write_all(&buffer[..read]).ok();
+            stdout.flush().ok();
+        }
+        println!("\n{}", disconnected);
+        std::process::exit(0);
+    });
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        writer.write_all(format!("{}\n", line).as_bytes())?;
+        if line.trim() == "quit" {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::document::OLDEST_AGE;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArgKind {
+    Text,
+    Rest,     // the rest of the line, words joined with spaces
+    Positive, // an integer above zero
+    Index,    // an integer from zero, e.g. an apartment number
+    Age,      // a resident's age, from 1 to OLDEST_AGE
+    Number,   // a non-negative amount, e.g. money
+    Choice(&'static [&'static str]),
+}
+
+impl ArgKind {
+    pub fn expected(&self) -> String {
+        match self {
+            ArgKind::Text | ArgKind::Rest => tr!("expected.text"),
+            ArgKind::Positive => tr!("expected.positive"),
+            ArgKind::Index => tr!("expected.index"),
+            ArgKind::Age => tr!("expected.age", OLDEST_AGE),
+            ArgKind::Number => tr!("expected.number"),
+            ArgKind::Choice(options) => tr!("expected.choice", options.join(", ")),
+        }
+    }
+
+    pub fn parse(&self, token: &str) -> Option<Value> {
+        match self {
+            ArgKind::Text | ArgKind::Rest => Some(Value::Text(token.to_string())),
+            ArgKind::Positive => token
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n > 0)
+                .map(Value::Integer),
+            ArgKind::Index => token.parse::<usize>().ok().map(Value::Integer),
+            ArgKind::Age => token
+                .parse::<usize>()
+                .ok()
+                .filter(|age| (1..=OLDEST_AGE).contains(age))
+                .map(Value::Integer),
+            ArgKind::Number => token
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite() && *n >= 0.0)
+                .map(Value::Number),
+            ArgKind::Choice(options) => options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(token))
+                .map(|option| Value::Text(option.to_string())),
+        }
+    }
+}
+
+pub struct Arg {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub optional: bool,
+}
+
+impl Arg {
+    pub const fn required(name: &'static str, kind: ArgKind) -> Self {
+        Self {
+            name,
+            kind,
+            optional: false,
+        }
+    }
+
+    pub const fn optional(name: &'static str, kind: ArgKind) -> Self {
+        Self {
+            name,
+            kind,
+            optional: true,
+        }
+    }
+}
+
+/// A command of one or more words (`hotel set`) followed by its arguments.
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub args: &'static [Arg],
+    pub about: &'static str, // key of the description in the message catalogue
+}
+
+impl CommandSpec {
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        for arg in self.args {
+            let dots = if arg.kind == ArgKind::Rest { "..." } else { "" };
+            let question = if arg.optional { "?" } else { "" };
+            usage.push_str(&format!(" [{}{}{}]", arg.name, dots, question));
+        }
+        usage
+    }
+
+    fn words(&self) -> impl Iterator<Item = &'static str> {
+        self.name.split(' ')
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Text(String),
+    Integer(usize),
+    Number(f64),
+}
+
+#[derive(Debug)]
+pub struct Command {
+    pub name: &'static str,
+    values: HashMap<&'static str, Value>,
+}
+
+impl Command {
+    pub fn get(&self, arg: &str) -> Option<&Value> {
+        self.values.get(arg)
+    }
+
+    /// The value of a command that takes a single argument.
+    pub fn only(&self) -> Option<&Value> {
+        match self.values.len() {
+            1 => self.values.values().next(),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, arg: &str) -> Option<&str> {
+        match self.get(arg)? {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn integer(&self, arg: &str) -> Option<usize> {
+        match self.get(arg)? {
+            Value::Integer(integer) => Some(*integer),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self, arg: &str) -> Option<f64> {
+        match self.get(arg)? {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CommandError {
+    Unknown(String),
+    Missing(&'static str),
+    Invalid { arg: &'static str, expected: String },
+    Unexpected(String),
+    UnclosedQuote,
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            CommandError::Unknown(name) => tr!("command.unknown", name),
+            CommandError::Missing(arg) => tr!("command.missing", arg),
+            CommandError::Invalid { arg, expected } => tr!("command.invalid", arg, expected),
+            CommandError::Unexpected(token) => tr!("command.unexpected", token),
+            CommandError::UnclosedQuote => tr!("command.unclosed_quote"),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// Splits a line into words, keeping "quoted text" and 'quoted text' together.
+/// A backslash escapes the next character.
+pub fn tokenize(line: &str) -> Result<Vec<String>, CommandError> {
+    let mut tokens = vec![];
+    let mut token: Option<String> = None;
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) => {
+                if let Some(escaped) = chars.next() {
+                    token.get_or_insert_with(String::new).push(escaped);
+                }
+            }
+            (c, Some(open)) if c == open => quote = None,
+            (c, Some(_)) => token.get_or_insert_with(String::new).push(c),
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                token.get_or_insert_with(String::new);
+            }
+            (c, None) if c.is_whitespace() => tokens.extend(token.take()),
+            (c, None) => token.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(CommandError::UnclosedQuote);
+    }
+    tokens.extend(token);
+    Ok(tokens)
+}
+
+/// The commands a state understands. Arguments are given in order, or by name as `age=30`.
+pub struct CommandRegistry {
+    pub commands: &'static [CommandSpec],
+}
+
+impl CommandRegistry {
+    pub const fn new(commands: &'static [CommandSpec]) -> Self {
+        Self { commands }
+    }
+
+    fn find(&self, input: &[&str]) -> Option<&CommandSpec> {
+        self.commands
+            .iter()
+            .filter(|spec| {
+                let words: Vec<&str> = spec.words().collect();
+                input.len() >= words.len() && input[..words.len()] == words[..]
+            })
+            .max_by_key(|spec| spec.words().count())
+    }
+
+    pub fn parse(&self, input: &[&str]) -> Result<Command, CommandError> {
+        let spec = self
+            .find(input)
+            .ok_or_else(|| CommandError::Unknown(input.first().unwrap_or(&"").to_string()))?;
+        let mut tokens = &input[spec.words().count()..];
+        let mut values = HashMap::new();
+        let mut positional = vec![];
+
+        while let Some((token, rest)) = tokens.split_first() {
+            let named = token.split_once('=').and_then(|(name, value)| {
+                let arg = spec.args.iter().find(|arg| arg.name == name)?;
+                Some((arg, value))
+            });
+            match named {
+                Some((arg, value)) => {
+                    values.insert(arg.name, Self::value(arg, value)?);
+                }
+                None => positional.push(*token),
+            }
+            tokens = rest;
+        }
+
+        let mut positional = positional.into_iter();
+        for arg in spec.args {
+            if values.contains_key(arg.name) {
+                continue;
+            }
+            let value = if arg.kind == ArgKind::Rest {
+                let rest: Vec<&str> = positional.by_ref().collect();
+                (!rest.is_empty()).then(|| rest.join(" "))
+            } else {
+                positional.next().map(|token| token.to_string())
+            };
+            match value {
+                Some(value) => {
+                    values.insert(arg.name, Self::value(arg, &value)?);
+                }
+                None if arg.optional => {}
+                None => return Err(CommandError::Missing(arg.name)),
+            }
+        }
+        if let Some(token) = positional.next() {
+            return Err(CommandError::Unexpected(token.to_string()));
+        }
+
+        Ok(Command {
+            name: spec.name,
+            values,
+        })
+    }
+
+    fn value(arg: &Arg, token: &str) -> Result<Value, CommandError> {
+        arg.kind.parse(token).ok_or_else(|| CommandError::Invalid {
+            arg: arg.name,
+            expected: arg.kind.expected(),
+        })
+    }
+
+    /// One line per command, or only the commands starting with `topic`.
+    pub fn help(&self, topic: Option<&str>) -> String {
+        let mut help = format!("{}\n", tr!("help.available"));
+        let commands = self
+            .commands
+            .iter()
+            .filter(|spec| topic.is_none_or(|topic| spec.name.starts_with(topic)));
+        for spec in commands {
+            help.push_str(&format!("{} -- {}\n", spec.usage(), tr!(spec.about)));
+        }
+        help
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: CommandRegistry = CommandRegistry::new(&[
+        CommandSpec {
+            name: "add resident",
+            args: &[
+                Arg::required("name", ArgKind::Text),
+                Arg::required("age", ArgKind::Age),
+                Arg::optional("balance", ArgKind::Number),
+            ],
+            about: "adds a resident",
+        },
+        CommandSpec {
+            name: "add",
+            args: &[Arg::required("note", ArgKind::Rest)],
+            about: "adds a note",
+        },
+        CommandSpec {
+            name: "flow",
+            args: &[Arg::required(
+                "sequence",
+                ArgKind::Choice(&["ordered", "random"]),
+            )],
+            about: "sets the flow",
+        },
+    ]);
+
+    fn parse(line: &str) -> Result<Command, CommandError> {
+        let tokens = tokenize(line)?;
+        let input: Vec<&str> = tokens.iter().map(String::as_str).collect();
+        REGISTRY.parse(&input)
+    }
+
+    #[test]
+    fn test_tokenize_keeps_quoted_words_together() {
+        assert_eq!(
+            tokenize(r#"add resident "Alice Johnson" 30 'x y' a\ b"#).unwrap(),
+            vec!["add", "resident", "Alice Johnson", "30", "x y", "a b"]
+        );
+        assert_eq!(
+            tokenize(r#"name="Bo Li" """#).unwrap(),
+            vec!["name=Bo Li", ""]
+        );
+        assert_eq!(tokenize("say \"hi"), Err(CommandError::UnclosedQuote));
+    }
+
+    #[test]
+    fn test_parse_typed_named_and_optional_arguments() {
+        let command = parse(r#"add resident age=30 "Alice Johnson""#).unwrap();
+        assert_eq!(command.name, "add resident");
+        assert_eq!(command.text("name"), Some("Alice Johnson"));
+        assert_eq!(command.integer("age"), Some(30));
+        assert_eq!(command.number("balance"), None);
+
+        let command = parse("add a longer note").unwrap();
+        assert_eq!(command.name, "add");
+        assert_eq!(command.text("note"), Some("a longer note"));
+
+        assert_eq!(
+            parse("FLOW Random").err(),
+            Some(CommandError::Unknown("FLOW".to_string()))
+        );
+        assert_eq!(
+            parse("flow Random").unwrap().text("sequence"),
+            Some("random")
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_are_precise() {
+        let message = |line: &str| parse(line).unwrap_err().to_string();
+
+        assert_eq!(
+            message("add resident Bob -3"),
+            "age must be an age from 1 to 120"
+        );
+        assert_eq!(
+            message("add resident Bob 18446744073709551615"),
+            "age must be an age from 1 to 120"
+        );
+        assert_eq!(message("add resident Bob"), "age is missing");
+        assert_eq!(
+            message("add resident Bob 3 balance=abc"),
+            "balance must be a non-negative number"
+        );
+        assert_eq!(
+            message("add resident Bob 3 1 extra"),
+            "unexpected argument 'extra'"
+        );
+        assert_eq!(
+            message("flow sideways"),
+            "sequence must be one of ordered, random"
+        );
+    }
+
+    #[test]
+    fn test_help_is_generated_from_the_registry() {
+        let help = REGISTRY.help(None);
+        assert!(help.contains("add resident [name] [age] [balance?] -- adds a resident\n"));
+        assert!(help.contains("add [note...] -- adds a note\n"));
+        assert!(!REGISTRY.help(Some("flow")).contains("add"));
+    }
+}
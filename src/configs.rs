@@ -0,0 +1,327 @@
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::command::{Arg, ArgKind, CommandRegistry, CommandSpec};
+use crate::hotel::Hotel;
+use crate::validation;
+
+const DIRECTORY: &str = "hotel_configs";
+
+/// The shape of the config files this build writes. Files without a version are from 1.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// `MIGRATIONS[n]` turns a config of version `n + 1` into one of version `n + 2`.
+const MIGRATIONS: [fn(&mut Map<String, Value>); 1] = [spell_out_rules_and_language];
+
+/// Version 1 knew nothing of house rules and languages; those hotels played by the defaults in English.
+fn spell_out_rules_and_language(config: &mut Map<String, Value>) {
+    config
+        .entry("house_rules")
+        .or_insert_with(|| json!({"rewind": true, "handoff": true}));
+    config.entry("language").or_insert_with(|| json!("en"));
+}
+
+const COMMANDS: CommandRegistry = CommandRegistry::new(&[
+    CommandSpec {
+        name: "list",
+        args: &[],
+        about: "about.configs.list",
+    },
+    CommandSpec {
+        name: "show",
+        args: &[Arg::required("id", ArgKind::Text)],
+        about: "about.configs.show",
+    },
+    CommandSpec {
+        name: "clone",
+        args: &[
+            Arg::required("id", ArgKind::Text),
+            Arg::required("new_id", ArgKind::Text),
+        ],
+        about: "about.configs.clone",
+    },
+    CommandSpec {
+        name: "rename",
+        args: &[
+            Arg::required("id", ArgKind::Text),
+            Arg::required("new_id", ArgKind::Text),
+        ],
+        about: "about.configs.rename",
+    },
+    CommandSpec {
+        name: "delete",
+        args: &[Arg::required("id", ArgKind::Text)],
+        about: "about.configs.delete",
+    },
+]);
+
+/// IDs end up in file names, so they are kept to letters, digits, `-` and `_`.
+fn check_id(id: &str) -> Result<(), String> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(tr!("configs.bad_id", id))
+    }
+}
+
+/// A hotel and the version it was saved with.
+fn parse(json: &str) -> Result<(Hotel, u64), String> {
+    let mut config: Map<String, Value> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let version = match config.remove("version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .filter(|&version| version >= 1)
+            .ok_or_else(|| tr!("configs.bad_version", version))?,
+    };
+    if version > SCHEMA_VERSION {
+        return Err(tr!("configs.too_new", version, SCHEMA_VERSION));
+    }
+    for migrate in &MIGRATIONS[version as usize - 1..] {
+        migrate(&mut config);
+    }
+    let hotel: Hotel = serde_json::from_value(Value::Object(config)).map_err(|e| e.to_string())?;
+    if let Some(problem) = validation::problems(&hotel).into_iter().next() {
+        return Err(problem.message);
+    }
+    Ok((hotel, version))
+}
+
+/// A directory of saved hotels, one `<ID>.json` each.
+pub struct Library {
+    directory: PathBuf,
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_DIRECTORY: std::cell::RefCell<Option<PathBuf>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Makes the default library of this test's thread a fresh temporary directory, so that
+/// saving tests leave the real one alone. Returns the directory.
+#[cfg(test)]
+pub fn use_temp_library(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("hotel-configs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    TEST_DIRECTORY.with(|test_directory| *test_directory.borrow_mut() = Some(directory.clone()));
+    directory
+}
+
+impl Default for Library {
+    fn default() -> Self {
+        #[cfg(test)]
+        if let Some(directory) = TEST_DIRECTORY.with(|directory| directory.borrow().clone()) {
+            return Self::new(directory);
+        }
+        Self::new(DIRECTORY)
+    }
+}
+
+impl Library {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.directory.join(format!("{}.json", id))
+    }
+
+    pub fn save(&self, hotel: &Hotel) -> io::Result<()> {
+        check_id(&hotel.id).map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        fs::create_dir_all(&self.directory)?;
+        let mut config = serde_json::to_value(hotel)?;
+        config["version"] = json!(SCHEMA_VERSION);
+        fs::write(self.path(&hotel.id), serde_json::to_string(&config)?)
+    }
+
+    /// The hotel saved as `id`, brought up to date; `None` if there is none.
+    pub fn load(&self, id: &str) -> Result<Option<Hotel>, String> {
+        check_id(id)?;
+        let path = self.path(id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json =
+            fs::read_to_string(&path).map_err(|e| tr!("configs.unreadable", path.display(), e))?;
+        parse(&json)
+            .map(|(hotel, _)| Some(hotel))
+            .map_err(|error| tr!("configs.invalid", path.display(), error))
+    }
+
+    /// The IDs of the saved hotels, in order.
+    fn ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = fs::read_dir(&self.directory)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    fn existing(&self, id: &str) -> Result<Hotel, String> {
+        self.load(id)?.ok_or_else(|| tr!("configs.not_found", id))
+    }
+
+    /// Runs `configs [list|show|clone|rename|delete] ...`.
+    pub fn run(&self, input: &[&str]) -> Result<String, String> {
+        if input.is_empty() {
+            return Ok(COMMANDS.help(None));
+        }
+        let command = COMMANDS.parse(input).map_err(|error| error.to_string())?;
+        match command.name {
+            "list" => {
+                let ids = self.ids();
+                if ids.is_empty() {
+                    return Ok(tr!("configs.empty"));
+                }
+                let lines: Vec<String> = ids
+                    .iter()
+                    .map(|id| {
+                        let summary = fs::read_to_string(self.path(id))
+                            .map_err(|e| e.to_string())
+                            .and_then(|json| parse(&json));
+                        match summary {
+                            Ok((hotel, version)) if version < SCHEMA_VERSION => tr!(
+                                "configs.outdated",
+                                tr!("configs.entry", id, hotel.num_rooms, hotel.capital),
+                                version
+                            ),
+                            Ok((hotel, _)) => {
+                                tr!("configs.entry", id, hotel.num_rooms, hotel.capital)
+                            }
+                            Err(error) => tr!("configs.broken", id, error),
+                        }
+                    })
+                    .collect();
+                Ok(lines.join("\n"))
+            }
+            "show" => {
+                let hotel = self.existing(command.text("id").unwrap())?;
+                Ok(hotel.describe_config())
+            }
+            "clone" | "rename" => {
+                let id = command.text("id").unwrap();
+                let new_id = command.text("new_id").unwrap();
+                let mut hotel = self.existing(id)?;
+                check_id(new_id)?;
+                if self.path(new_id).exists() {
+                    return Err(tr!("configs.taken", new_id));
+                }
+                hotel.id = new_id.to_string();
+                self.save(&hotel)
+                    .map_err(|e| tr!("configs.save_failed", new_id, e))?;
+                if command.name == "rename" {
+                    fs::remove_file(self.path(id))
+                        .map_err(|e| tr!("configs.delete_failed", id, e))?;
+                    return Ok(tr!("configs.renamed", id, new_id));
+                }
+                Ok(tr!("configs.cloned", id, new_id))
+            }
+            "delete" => {
+                let id = command.text("id").unwrap();
+                check_id(id)?;
+                if !self.path(id).exists() {
+                    return Err(tr!("configs.not_found", id));
+                }
+                fs::remove_file(self.path(id)).map_err(|e| tr!("configs.delete_failed", id, e))?;
+                Ok(tr!("configs.deleted", id))
+            }
+            _ => unreachable!("every registered command is handled"),
+        }
+    }
+}
+
+/// Saves `hotel` to the hotel_configs directory.
+pub fn save(hotel: &Hotel) -> io::Result<()> {
+    Library::default().save(hotel)
+}
+
+/// The hotel saved as `id` in the hotel_configs directory; `None` if there is none.
+pub fn load(id: &str) -> Result<Option<Hotel>, String> {
+    Library::default().load(id)
+}
+
+/// Runs `configs ...` on the hotel_configs directory.
+pub fn run(input: &[&str]) -> Result<String, String> {
+    Library::default().run(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_old_configs_are_migrated() {
+        let old = r#"{"id":"old","num_rooms":12,"capital":500.0,"building_type":"Rectangular","elevator_position":2,"rooms_per_story":4,"entrance_fee":10.0,"daily_costs":2.0}"#;
+
+        let (hotel, version) = parse(old).unwrap();
+
+        assert_eq!(version, 1);
+        assert_eq!((hotel.id.as_str(), hotel.num_rooms), ("old", 12));
+        assert!(hotel.house_rules.rewind);
+        match parse(r#"{"id":"new","version":99}"#) {
+            Err(error) => assert!(error.contains("99")),
+            Ok(_) => panic!("a config from a newer schema was loaded"),
+        }
+        assert!(parse("not json").is_err());
+        assert!(parse(&old.replace("\"rooms_per_story\":4", "\"rooms_per_story\":1")).is_err());
+    }
+
+    #[test]
+    fn test_configs_commands_manage_the_library() {
+        let directory = std::env::temp_dir().join(format!("hotel-configs-{}", std::process::id()));
+        let library = Library::new(&directory);
+        let mut hotel = Hotel::for_test(9, 3, &[]);
+        hotel.id = "library-test".to_string();
+        library.save(&hotel).unwrap();
+
+        assert!(library.run(&["list"]).unwrap().contains("library-test"));
+        assert!(library
+            .run(&["show", "library-test"])
+            .unwrap()
+            .contains('9'));
+        library
+            .run(&["clone", "library-test", "library-test-copy"])
+            .unwrap();
+        assert!(library
+            .run(&["clone", "library-test", "library-test-copy"])
+            .is_err());
+        library
+            .run(&["rename", "library-test-copy", "library-test-moved"])
+            .unwrap();
+        assert_eq!(
+            library
+                .load("library-test-moved")
+                .unwrap()
+                .unwrap()
+                .num_rooms,
+            9
+        );
+        assert!(library.load("library-test-copy").unwrap().is_none());
+        library.run(&["delete", "library-test"]).unwrap();
+        library.run(&["delete", "library-test-moved"]).unwrap();
+        assert!(library.run(&["delete", "library-test"]).is_err());
+        assert!(library.run(&["show", "../secrets"]).is_err());
+        hotel.id = "../escaped".to_string();
+        assert!(library.save(&hotel).is_err());
+        assert!(!directory.join("../escaped.json").exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
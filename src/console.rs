@@ -0,0 +1,303 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::net::TcpStream;
+use std::rc::Rc;
+
+use crossterm::{
+    cursor::MoveTo,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{self, Clear, ClearType},
+};
+
+use crate::hotel::Hotel;
+
+/// Writes a line to the player who is currently in focus (stdout by default).
+macro_rules! say {
+    () => {
+        $crate::console::write("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::console::write(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub trait Channel {
+    fn write(&mut self, text: &str) -> io::Result<()>;
+    fn read_line(&mut self) -> io::Result<Option<String>>;
+
+    /// Wipes what was shown so far, where that makes sense.
+    fn clear(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub struct TcpChannel {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl TcpChannel {
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+}
+
+impl Channel for TcpChannel {
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        self.writer.write_all(text.as_bytes())?;
+        self.writer.flush()
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line))
+    }
+}
+
+/// Replays prepared answers and records everything written, for players driven by a program.
+pub struct BufferChannel {
+    inputs: VecDeque<String>,
+    output: Rc<RefCell<String>>,
+}
+
+impl BufferChannel {
+    pub fn new(inputs: Vec<String>) -> Self {
+        Self {
+            inputs: inputs.into(),
+            output: Rc::new(RefCell::new(String::new())),
+        }
+    }
+
+    pub fn output(&self) -> Rc<RefCell<String>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl Channel for BufferChannel {
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        self.output.borrow_mut().push_str(text);
+        Ok(())
+    }
+
+    // once the answers run out, the input has ended
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let line = self.inputs.pop_front();
+        if let Some(line) = &line {
+            self.output.borrow_mut().push_str(&format!("{}\n", line));
+        }
+        Ok(line)
+    }
+}
+
+type TargetPicker = Box<dyn FnMut(&Hotel, &[usize]) -> Option<usize>>;
+
+#[derive(Default)]
+struct Console {
+    channels: HashMap<usize, Box<dyn Channel>>,
+    focus: Option<usize>,
+    host: Option<Box<dyn Channel>>, // replaces stdin/stdout of the host when set
+    target_picker: Option<TargetPicker>,
+}
+
+thread_local! {
+    static CONSOLE: RefCell<Console> = RefCell::new(Console::default());
+}
+
+/// Replaces the host's stdin/stdout, e.g. with a full-screen terminal UI, returning the previous host.
+pub fn set_host(channel: Option<Box<dyn Channel>>) -> Option<Box<dyn Channel>> {
+    CONSOLE.with(|console| std::mem::replace(&mut console.borrow_mut().host, channel))
+}
+
+/// Lets the players choose target apartments with `picker` instead of typing their numbers.
+pub fn set_target_picker(picker: Option<TargetPicker>) {
+    CONSOLE.with(|console| console.borrow_mut().target_picker = picker);
+}
+
+pub fn pick_target(hotel: &Hotel, options: &[usize]) -> Option<usize> {
+    let mut picker = CONSOLE.with(|console| console.borrow_mut().target_picker.take())?;
+    let target = picker(hotel, options);
+    CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        if console.target_picker.is_none() {
+            console.target_picker = Some(picker);
+        }
+    });
+    target
+}
+
+/// Routes the prompts and private output of the resident of `apartment` to `channel`.
+pub fn register(apartment: usize, channel: Box<dyn Channel>) {
+    CONSOLE.with(|console| console.borrow_mut().channels.insert(apartment, channel));
+}
+
+pub fn unregister(apartment: usize) {
+    CONSOLE.with(|console| console.borrow_mut().channels.remove(&apartment));
+}
+
+pub fn is_registered(apartment: usize) -> bool {
+    CONSOLE.with(|console| console.borrow().channels.contains_key(&apartment))
+}
+
+pub fn registered_count() -> usize {
+    CONSOLE.with(|console| console.borrow().channels.len())
+}
+
+/// Sets the apartment whose channel receives the output, returning the previous focus.
+pub fn focus(apartment: Option<usize>) -> Option<usize> {
+    CONSOLE.with(|console| std::mem::replace(&mut console.borrow_mut().focus, apartment))
+}
+
+pub fn write(text: &str) {
+    let delivered = CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        let focus = console.focus;
+        let channel = focus.and_then(|focus| console.channels.get_mut(&focus));
+        channel.map(|channel| channel.write(text).ok())
+    });
+    if delivered.is_none() {
+        write_host(text);
+    }
+}
+
+fn write_host(text: &str) {
+    let delivered = CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        console.host.as_mut().map(|host| host.write(text).ok())
+    });
+    if delivered.is_none() {
+        print!("{}", text);
+        io::stdout().flush().unwrap();
+    }
+}
+
+/// Wipes the host's screen, so the next player at the keyboard can't read the last one's turn.
+pub fn clear() {
+    let cleared = CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        console.host.as_mut().map(|host| host.clear().ok())
+    });
+    if cleared.is_none() && io::stdout().is_terminal() {
+        execute!(io::stdout(), Clear(ClearType::All), MoveTo(0, 0)).ok();
+    }
+}
+
+/// Writes to the host's terminal and to every connected player.
+pub fn broadcast(text: &str) {
+    write_host(&format!("{}\n", text));
+    CONSOLE.with(|console| {
+        for channel in console.borrow_mut().channels.values_mut() {
+            channel.write(&format!("{}\n", text)).ok();
+        }
+    });
+}
+
+/// Reads a line from the player in focus. If their connection is lost, the host answers instead.
+/// `None` once the host's input has ended, e.g. a script ran out of answers.
+pub fn read_line() -> Option<String> {
+    let focus = CONSOLE.with(|console| console.borrow().focus);
+    if let Some(apartment) = focus.filter(|&apartment| is_registered(apartment)) {
+        if let Some(line) = read_line_from(apartment) {
+            return Some(line);
+        }
+        write_host(&format!("{}\n", tr!("server.host_answers", apartment)));
+    }
+    let line = CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        console.host.as_mut().map(|host| host.read_line())
+    });
+    match line {
+        Some(line) => line.ok().flatten(),
+        None => {
+            let mut input = String::new();
+            match io::stdin().read_line(&mut input) {
+                Ok(0) | Err(_) => None,
+                Ok(_) => Some(input),
+            }
+        }
+    }
+}
+
+/// Reads a line from the channel of `apartment`, unregistering it once the connection is lost.
+pub fn read_line_from(apartment: usize) -> Option<String> {
+    let line = CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        let channel = console.channels.get_mut(&apartment)?;
+        channel.read_line().ok().flatten()
+    });
+    if line.is_none() {
+        unregister(apartment);
+    }
+    line
+}
+
+pub fn prompt(text: &str) -> Option<String> {
+    write(text);
+    read_line()
+}
+
+/// Like `prompt`, but what is typed at the host's terminal isn't shown, e.g. for a PIN.
+pub fn prompt_secret(text: &str) -> Option<String> {
+    let at_terminal = CONSOLE.with(|console| {
+        let console = console.borrow();
+        let remote = console
+            .focus
+            .is_some_and(|focus| console.channels.contains_key(&focus));
+        console.host.is_none() && !remote
+    });
+    if !at_terminal || !io::stdin().is_terminal() {
+        return prompt(text);
+    }
+    write(text);
+    let line = read_hidden_line();
+    write_host("\n");
+    line
+}
+
+/// Switches the terminal to raw mode until dropped, even if the code using it panics.
+pub struct RawMode;
+
+impl RawMode {
+    pub fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        terminal::disable_raw_mode().ok();
+    }
+}
+
+/// Reads keys up to Enter without echoing them; `None` on Ctrl+D or Ctrl+C.
+fn read_hidden_line() -> Option<String> {
+    let _raw = RawMode::enable().ok()?;
+    let mut line = String::new();
+    loop {
+        let Event::Key(key) = event::read().ok()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Some(line),
+            KeyCode::Backspace => {
+                line.pop();
+            }
+            KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return None
+            }
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+    }
+}
@@ -1,7 +1,13 @@
-use chrono::Datelike;
+use chrono::{Datelike, Duration, NaiveDate};
 use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 
-use rand::thread_rng;
+use std::fmt;
+
+use crate::{locale, random, roles::Role, text_formatters::format_to_length};
 
 struct PhotoCreator {
     haircuts: Vec<char>,
@@ -26,46 +32,103 @@ impl PhotoCreator {
         }
     }
 
-    fn generate_photo(&self) -> Vec<String> {
-        let mut rng = thread_rng();
+    fn generate_face(&self) -> Face {
+        let mut rng = random::rng();
+
+        Face {
+            haircut: *self.haircuts.choose(&mut rng).unwrap(),
+            eyes: *self.eyes.choose(&mut rng).unwrap(),
+            nose: *self.noses.choose(&mut rng).unwrap(),
+            mouth: *self.mouths.choose(&mut rng).unwrap(),
+        }
+    }
+}
+
+/// The features of a face, as a resident really looks and as an ID photo shows them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Face {
+    pub haircut: char,
+    pub eyes: char,
+    pub nose: char,
+    pub mouth: char,
+}
 
-        let haircut = self.haircuts.choose(&mut rng).unwrap();
-        let eye = self.eyes.choose(&mut rng).unwrap();
-        let nose = self.noses.choose(&mut rng).unwrap();
-        let mouth = self.mouths.choose(&mut rng).unwrap();
+impl Face {
+    pub fn random() -> Self {
+        PhotoCreator::new().generate_face()
+    }
 
+    /// How many of the four features two faces share.
+    pub fn resemblance(&self, other: &Face) -> usize {
+        [
+            self.haircut == other.haircut,
+            self.eyes == other.eyes,
+            self.nose == other.nose,
+            self.mouth == other.mouth,
+        ]
+        .iter()
+        .filter(|same| **same)
+        .count()
+    }
+
+    fn photo(&self) -> Vec<String> {
         vec![
-            format!("  {}  ", haircut.to_string().repeat(9)),
-            format!("  | {0}   {0} |  ", eye),
-            format!("  |   {}   |  ", nose),
-            format!("   \\  {}  /   ", mouth),
-            format!("/¯¯¯¯¯v¯¯¯¯¯\\"),
+            format!("  {}  ", self.haircut.to_string().repeat(9)),
+            format!("  | {0}   {0} |  ", self.eyes),
+            format!("  |   {}   |  ", self.nose),
+            format!("   \\  {}  /   ", self.mouth),
+            "/¯¯¯¯¯v¯¯¯¯¯\\".to_string(),
         ]
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// What gives a forged document away.
+#[derive(EnumIter, Debug, Clone, Copy, PartialEq)]
+pub enum Flaw {
+    MismatchedPhoto,
+    ImpossibleBirthYear,
+    InvalidNumber,
+}
+
+impl fmt::Display for Flaw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tr!(&format!("flaw.{:?}", self)))
+    }
+}
+
+/// Nobody is older than this; a document saying otherwise is wrong.
+pub const OLDEST_AGE: usize = 120;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Document {
     pub role: Role,
     pub name: String,
     pub year_of_birth: usize,
     pub title: String,
     pub accomplishments: Vec<String>,
+    pub photo: Face,
+    pub number: String, // two letters and six digits, the last one a check digit
+    pub issued: NaiveDate,
+    #[serde(default)]
+    pub traits: Vec<String>, // the character of the holder, for those who have one on record
+    #[serde(default)]
+    pub forged: bool, // made by the Swindler; a genuine one may still be carried by someone else
 }
 
 impl Document {
-    pub fn new(role: Role, name: String, age: usize) -> Document {
-        let current_year: usize = chrono::Utc::now().year().try_into().unwrap();
-        let year_of_birth = current_year - age;
-
-        let title = all_titles(&role)
-            .choose(&mut rand::thread_rng())
-            .unwrap()
-            .to_string();
+    /// A document of whoever has `face`, issued some time in the last ten years (but not before they were born).
+    pub fn new(role: Role, name: String, age: usize, face: Face) -> Document {
+        let mut rng = random::rng();
+        let today = chrono::Utc::now().date_naive();
+        let current_year: usize = today.year().try_into().unwrap();
+        let year_of_birth = current_year.saturating_sub(age);
+
+        let title = all_titles(&role).choose(&mut rng).unwrap().clone();
         let accomplishments = all_accomplishments(&role)
-            .choose_multiple(&mut rand::thread_rng(), 3) // Choose 3 random accomplishments
-            .map(|s| s.to_string())
+            .choose_multiple(&mut rng, 3) // Choose 3 random accomplishments
+            .cloned()
             .collect();
+        let days_ago = rng.gen_range(0..=age.saturating_mul(365).min(10 * 365)) as i64;
 
         Document {
             role,
@@ -73,176 +136,132 @@ impl Document {
             year_of_birth,
             title,
             accomplishments,
+            photo: face,
+            number: Self::generate_number(),
+            issued: today - Duration::days(days_ago),
+            traits: vec![],
+            forged: false,
         }
     }
-}
 
-pub fn all_titles(role: &Role) -> Vec<&'static str> {
-    match role {
-        Role::Killer => vec![
-            "Master Assassin",
-            "Stealth Operative",
-            "Hitman",
-            "Underworld Enforcer",
-            "Silent Executioner",
-            "Professional Eliminator",
-        ],
-        Role::Police => vec![
-            "Chief Inspector",
-            "Head of Forensics",
-            "Detective Sergeant",
-            "Special Investigator",
-            "Undercover Operative",
-            "Anti-Crime Unit Leader",
-        ],
-        Role::Doctor => vec![
-            "Chief Surgeon",
-            "Head of Cardiology",
-            "Senior Consultant in Pediatrics",
-            "Leading Medical Researcher",
-            "Pharmaceutical Innovator",
-            "Head of Emergency Medicine",
-        ],
-        Role::Janitor => vec![
-            "Senior Custodian",
-            "Head Janitor",
-            "Facility Maintenance Manager",
-            "Cleanup Specialist",
-            "Sanitation Expert",
-            "Biohazard Remediation Technician",
-        ],
-        Role::OldLady => vec![
-            "Neighborhood Watch Leader",
-            "Veteran Community Observer",
-            "Senior Intelligence Gatherer",
-            "Local Historian",
-            "Community Matriarch",
-            "Elder Informant",
-        ],
-        Role::Swindler => vec![
-            "Master Forger",
-            "Con Artist",
-            "Identity Thief",
-            "Deception Specialist",
-            "Information Manipulator",
-            "Professional Liar",
-        ],
-        Role::Avenger => vec![
-            "Vengeful Spirit",
-            "Retribution Seeker",
-            "Justice Enforcer",
-            "Punisher",
-            "Guardian of Vengeance",
-            "Nemesis",
-        ],
-        Role::Judge => vec![
-            "Chief Justice",
-            "Senior Judge",
-            "Verdict Deliverer",
-            "Courtroom Authority",
-            "Legal Arbiter",
-            "Supreme Adjudicator",
-        ],
-        Role::Professor => vec![
-            "Senior Scientist in Natural Sciences",
-            "Head of Physics Department",
-            "Lead Researcher in AI",
-            "Distinguished Professor of Chemistry",
-            "Renowned Astrophysicist",
-            "Pioneer in Genetic Engineering",
-        ],
+    /// A fake `role` document of whoever has `face`, made in a hurry: it has at least one flaw.
+    pub fn forge(role: Role, name: String, age: usize, face: Face) -> Document {
+        let mut rng = random::rng();
+        let mut document = Self::new(role, name, age, face);
+        document.forged = true;
+        let mut flaws: Vec<Flaw> = Flaw::iter().filter(|_| rng.gen_bool(0.5)).collect();
+        if flaws.is_empty() {
+            flaws.push(
+                *Flaw::iter()
+                    .collect::<Vec<Flaw>>()
+                    .choose(&mut rng)
+                    .unwrap(),
+            );
+        }
+        for flaw in flaws {
+            match flaw {
+                Flaw::MismatchedPhoto => {
+                    while document.resembles(&face) {
+                        document.photo = Face::random();
+                    }
+                }
+                Flaw::ImpossibleBirthYear => {
+                    let issued: usize = document.issued.year().try_into().unwrap();
+                    document.year_of_birth = if rng.gen_bool(0.5) {
+                        issued + rng.gen_range(1..=30)
+                    } else {
+                        issued - OLDEST_AGE - rng.gen_range(10..=80)
+                    };
+                }
+                Flaw::InvalidNumber => {
+                    let (number, check) = document.number.split_at(7);
+                    let wrong = (check.parse::<u32>().unwrap() + rng.gen_range(1..10)) % 10;
+                    document.number = format!("{}{}", number, wrong);
+                }
+            }
+        }
+        document
     }
-}
 
-pub fn all_accomplishments(role: &Role) -> Vec<&'static str> {
-    match role {
-        Role::Killer => vec![
-            "Completed 50+ high-profile assassinations",
-            "Trained in 5 different martial arts",
-            "Expert in stealth and evasion",
-            "Master of disguise",
-            "Successfully evaded capture for 10+ years",
-            "Known for precision and efficiency",
-        ],
-        Role::Police => vec![
-            "Solved 100+ cases",
-            "Awarded Officer of the Year",
-            "Led major anti-drug operation",
-            "Specialized in cybercrime investigation",
-            "Received bravery commendation",
-            "Keynote speaker at law enforcement conferences",
-        ],
-        Role::Doctor => vec![
-            "Invented a new heart surgery technique",
-            "Published groundbreaking research on cancer treatment",
-            "Developed a new vaccine",
-            "Awarded Medical Innovator of the Year",
-            "Authored 10+ medical textbooks",
-            "Led international health missions",
-        ],
-        Role::Janitor => vec![
-            "Recognized for exceptional cleanliness standards",
-            "Specialized in hazardous waste disposal",
-            "Trained in emergency response procedures",
-            "Implemented new sanitation protocols",
-            "Awarded Employee of the Year",
-            "Expert in facility management",
-        ],
-        Role::OldLady => vec![
-            "Gathered crucial information leading to arrests",
-            "Recognized as a pillar of the community",
-            "Collected extensive local history records",
-            "Helped resolve neighborhood disputes",
-            "Awarded Community Service Medal",
-            "Known for keen observation skills",
-        ],
-        Role::Swindler => vec![
-            "Successfully forged 100+ documents",
-            "Executed large-scale identity theft",
-            "Manipulated key information in major cases",
-            "Trained in advanced deception techniques",
-            "Awarded Master of Deception",
-            "Known for impeccable forgeries",
-        ],
-        Role::Avenger => vec![
-            "Avenged 50+ wronged individuals",
-            "Trained in hand-to-hand combat",
-            "Expert in tracking and surveillance",
-            "Known for delivering swift justice",
-            "Awarded Protector of the Innocent",
-            "Feared by wrongdoers",
-        ],
-        Role::Judge => vec![
-            "Presided over 500+ trials",
-            "Authored influential legal opinions",
-            "Recognized for judicial fairness",
-            "Keynote speaker at legal conferences",
-            "Awarded Judge of the Year",
-            "Known for upholding justice",
-        ],
-        Role::Professor => vec![
-            "Calmness syrop inventor",
-            "Exceptional achievements in human brain research",
-            "Nobel Prize in Physics",
-            "Published 50+ scientific papers",
-            "Keynote speaker at international conferences",
-            "Patented 10+ innovations",
-        ],
+    /// Everything wrong with the document when held by someone with `face`; nothing for their own genuine one.
+    pub fn flaws(&self, face: &Face) -> Vec<Flaw> {
+        let issued: usize = self.issued.year().try_into().unwrap_or(0);
+        Flaw::iter()
+            .filter(|flaw| match flaw {
+                Flaw::MismatchedPhoto => !self.resembles(face),
+                Flaw::ImpossibleBirthYear => {
+                    self.year_of_birth > issued || issued - self.year_of_birth > OLDEST_AGE
+                }
+                Flaw::InvalidNumber => !self.has_valid_number(),
+            })
+            .collect()
+    }
+
+    fn check_digit(digits: &str) -> Option<u32> {
+        digits
+            .chars()
+            .map(|c| c.to_digit(10))
+            .sum::<Option<u32>>()
+            .map(|sum| sum % 10)
+    }
+
+    fn generate_number() -> String {
+        let mut rng = random::rng();
+        let letters: String = (0..2).map(|_| rng.gen_range('A'..='Z')).collect();
+        let digits: String = (0..5)
+            .map(|_| char::from_digit(rng.gen_range(0..10), 10).unwrap())
+            .collect();
+        let check = Self::check_digit(&digits).unwrap();
+        format!("{}{}{}", letters, digits, check)
+    }
+
+    /// Whether the number is two capital letters and six digits that add up to the check digit.
+    pub fn has_valid_number(&self) -> bool {
+        let (letters, digits) = self.number.split_at(self.number.len().min(2));
+        letters.len() == 2
+            && letters.chars().all(|c| c.is_ascii_uppercase())
+            && digits.len() == 6
+            && Self::check_digit(&digits[..5])
+                == digits[5..].chars().next().and_then(|c| c.to_digit(10))
+    }
+
+    /// Whether the photo is of `face`: everyone looks a little different from their ID photo,
+    /// so one feature may differ.
+    pub fn resembles(&self, face: &Face) -> bool {
+        self.photo.resemblance(face) >= 3
     }
 }
 
-use std::fmt;
+pub fn all_titles(role: &Role) -> Vec<String> {
+    locale::list(&format!("document.titles.{:?}", role))
+}
+
+pub fn all_accomplishments(role: &Role) -> Vec<String> {
+    locale::list(&format!("document.accomplishments.{:?}", role))
+}
 
-use crate::{roles::Role, text_formatters::format_to_length};
+/// Documents as their holder would show them, numbered from 0.
+pub fn list(documents: &[Document]) -> String {
+    documents
+        .iter()
+        .enumerate()
+        .map(|(index, document)| format!("{}.\n{}", index, document))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
 
 impl fmt::Display for Document {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let photo = PhotoCreator::new().generate_photo();
+        let photo = self.photo.photo();
         const DOCUMENT_WIDTH: usize = 50;
         const PHOTO_WIDTH: usize = 13;
         const BORDERS_WIDTH: usize = 3;
         const TEXT_WIDTH: usize = DOCUMENT_WIDTH - BORDERS_WIDTH - PHOTO_WIDTH;
-        let horizontal_line = &format!("+{:-^width$}+", "DOCUMENT", width = DOCUMENT_WIDTH - 2);
+        let horizontal_line = &format!(
+            "+{:-^width$}+",
+            tr!("document.header"),
+            width = DOCUMENT_WIDTH - 2
+        );
 
         let mut output = String::new();
         output.push_str(&format!("{}\n", horizontal_line));
@@ -258,7 +277,7 @@ impl fmt::Display for Document {
         output.push_str(&format!(
             "|{:^}|{}|\n",
             photo[2],
-            format_to_length(&format!("{:?}", self.role), TEXT_WIDTH)
+            format_to_length(&self.role.to_string(), TEXT_WIDTH)
         ));
         output.push_str(&format!(
             "|{:^}|{}|\n",
@@ -280,7 +299,77 @@ impl fmt::Display for Document {
             "",
             format_to_length(&format!("{:?}", self.accomplishments[2]), TEXT_WIDTH)
         ));
+        if !self.traits.is_empty() {
+            output.push_str(&format!(
+                "|{:^PHOTO_WIDTH$}|{}|\n",
+                "",
+                format_to_length(&tr!("document.traits", self.traits.join(", ")), TEXT_WIDTH)
+            ));
+        }
+        output.push_str(&format!(
+            "|{:^PHOTO_WIDTH$}|{}|\n",
+            "",
+            format_to_length(
+                &tr!(
+                    "document.number",
+                    self.number,
+                    self.issued.format("%Y-%m-%d")
+                ),
+                TEXT_WIDTH
+            )
+        ));
         output.push_str(&format!("{}\n", horizontal_line));
         write!(f, "{}", output)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_keeps_its_photo_and_number() {
+        let face = Face::random();
+        let mut document = Document::new(Role::Police, "Pat".to_string(), 30, face);
+
+        assert_eq!(document.to_string(), document.to_string());
+        assert!(document.resembles(&face));
+        assert!(document.has_valid_number());
+        let json = serde_json::to_string(&document).unwrap();
+        assert_eq!(serde_json::from_str::<Document>(&json).unwrap(), document);
+
+        let stranger = Face {
+            haircut: if face.haircut == '#' { '@' } else { '#' },
+            eyes: if face.eyes == 'o' { 'O' } else { 'o' },
+            ..face
+        };
+        assert!(!document.resembles(&stranger));
+        document.number = "AB123450".to_string();
+        assert!(!document.has_valid_number());
+        document.number = "AB123455".to_string();
+        assert!(document.has_valid_number());
+    }
+
+    #[test]
+    fn test_forged_documents_have_flaws() {
+        for _ in 0..50 {
+            let face = Face::random();
+            let genuine = Document::new(Role::Killer, "Kim".to_string(), 40, face);
+            let forged = Document::forge(Role::Doctor, "Kim".to_string(), 40, face);
+
+            assert!(genuine.flaws(&face).is_empty());
+            assert!(!forged.flaws(&face).is_empty());
+            assert!(forged.forged && !genuine.forged);
+            // a genuine document in someone else's hands only gives its photo away
+            let stranger = Face {
+                haircut: if face.haircut == '/' { '\\' } else { '/' },
+                eyes: if face.eyes == 'o' { 'O' } else { 'o' },
+                ..face
+            };
+            assert_eq!(genuine.flaws(&stranger), [Flaw::MismatchedPhoto]);
+            assert_eq!(forged.role, Role::Doctor);
+        }
+        let ancient = Document::new(Role::Judge, "Old".to_string(), usize::MAX, Face::random());
+        assert_eq!(ancient.year_of_birth, 0);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::bulletin_board::NoticeKind;
+use crate::hotel::Hotel;
+use crate::resident::{Status, SuperStatus};
+use crate::scope::Viewer;
+
+const CELL_WIDTH: usize = 150;
+const LINE_HEIGHT: usize = 18;
+const SHAFT_WIDTH: usize = 30;
+const MARGIN: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Svg,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "svg" => Some(ExportFormat::Svg),
+            "html" => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+// (label, fill) of every legend entry, in the order they are drawn
+const LEGEND: [(&str, &str); 6] = [
+    ("Alive", "#b7e4b0"),
+    ("Wounded", "#f7d08a"),
+    ("Arrested", "#a9c8f0"),
+    ("Dead", "#e89a9a"),
+    ("Vacant", "#eeeeee"),
+    ("Locked", "url(#locked)"),
+];
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn status_fill(hotel: &Hotel, idx: usize, viewer: Option<&Viewer>) -> &'static str {
+    let Some(resident) = &hotel.apartments[idx].resident else {
+        return LEGEND[4].1;
+    };
+    let resident = resident.lock().unwrap();
+    if viewer.is_some_and(|viewer| !viewer.lives_in(idx)) {
+        let reported = hotel.bulletin_board.has_notice(NoticeKind::Death, idx);
+        return if reported { LEGEND[3].1 } else { LEGEND[0].1 };
+    }
+    match (resident.status, resident.super_status) {
+        (Status::Dead, _) => LEGEND[3].1,
+        (_, SuperStatus::Arrested) => LEGEND[2].1,
+        (_, SuperStatus::Wounded) => LEGEND[1].1,
+        _ => LEGEND[0].1,
+    }
+}
+
+/// Draws the same floors as `Hotel::print_detailed`, one line of text per style character.
+pub fn render_svg(hotel: &Hotel, style: &str, viewer: Option<&Viewer>) -> String {
+    let floors = hotel.floor_plan();
+    let cell_height = LINE_HEIGHT * (style.chars().count() + 1);
+    let rooms = hotel.rooms_per_story;
+    let shaft = if hotel.elevator_position <= rooms && rooms > 0 {
+        SHAFT_WIDTH
+    } else {
+        0
+    };
+    let width = 2 * MARGIN + rooms * CELL_WIDTH + shaft;
+    let legend_top = MARGIN + floors.len() * cell_height + MARGIN;
+    let height = legend_top + LINE_HEIGHT * 2;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"12\">\n",
+        width, height
+    );
+    svg.push_str(concat!(
+        "<defs><pattern id=\"locked\" width=\"8\" height=\"8\" patternUnits=\"userSpaceOnUse\" patternTransform=\"rotate(45)\">",
+        "<rect width=\"8\" height=\"8\" fill=\"#999999\"/><line x1=\"0\" y1=\"0\" x2=\"0\" y2=\"8\" stroke=\"#555555\" stroke-width=\"4\"/>",
+        "</pattern></defs>\n"
+    ));
+    svg.push_str(&format!(
+        "<title>Hotel {}</title>\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+        escape(&hotel.id)
+    ));
+
+    for (row, floor) in floors.into_iter().enumerate() {
+        let y = MARGIN + row * cell_height;
+        let mut x = MARGIN;
+        for (room, idx) in floor.into_iter().enumerate() {
+            if room == hotel.elevator_position {
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#444444\"/>\n",
+                    x, y, SHAFT_WIDTH, cell_height
+                ));
+                x += SHAFT_WIDTH;
+            }
+            if let Some(idx) = idx {
+                svg.push_str(&render_apartment(
+                    hotel,
+                    idx,
+                    style,
+                    viewer,
+                    (x, y, cell_height),
+                ));
+            }
+            x += CELL_WIDTH;
+        }
+        if hotel.elevator_position == rooms {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#444444\"/>\n",
+                x, y, SHAFT_WIDTH, cell_height
+            ));
+        }
+    }
+
+    let mut x = MARGIN;
+    for (label, fill) in LEGEND {
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"14\" height=\"14\" fill=\"{}\" stroke=\"#333333\"/>\n<text x=\"{}\" y=\"{}\">{}</text>\n",
+            x,
+            legend_top,
+            fill,
+            x + 18,
+            legend_top + 11,
+            label
+        ));
+        x += 100;
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn render_apartment(
+    hotel: &Hotel,
+    idx: usize,
+    style: &str,
+    viewer: Option<&Viewer>,
+    (x, y, height): (usize, usize, usize),
+) -> String {
+    let apartment = &hotel.apartments[idx];
+    let mut cell = format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#333333\"/>\n",
+        x,
+        y,
+        CELL_WIDTH,
+        height,
+        status_fill(hotel, idx, viewer)
+    );
+    if apartment.is_locked() {
+        cell.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"0.5\"/>\n",
+            x, y, CELL_WIDTH, height, LEGEND[5].1
+        ));
+    }
+    for (line, param) in style.chars().enumerate() {
+        let detail = hotel.format_apartment_detail(apartment, param, viewer);
+        cell.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\">{}</text>\n",
+            x + 6,
+            y + LINE_HEIGHT * (line + 1),
+            escape(&detail)
+        ));
+    }
+    cell
+}
+
+/// A page that needs nothing but a browser: the SVG is inlined.
+pub fn render_html(hotel: &Hotel, style: &str, viewer: Option<&Viewer>) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Hotel {id}</title>\n\
+         <style>body {{ font-family: sans-serif; background: #fafafa; }}</style>\n</head>\n<body>\n\
+         <h1>Hotel {id}</h1>\n{svg}</body>\n</html>\n",
+        id = escape(&hotel.id),
+        svg = render_svg(hotel, style, viewer)
+    )
+}
+
+pub fn render(hotel: &Hotel, format: ExportFormat, style: &str, viewer: Option<&Viewer>) -> String {
+    match format {
+        ExportFormat::Svg => render_svg(hotel, style, viewer),
+        ExportFormat::Html => render_html(hotel, style, viewer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roles::Role;
+
+    fn hotel_with_guest() -> Hotel {
+        let mut hotel = Hotel::for_test(4, 2, &[("<Bob>", Role::Killer)]);
+        hotel.apartments[0]
+            .resident
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .status = Status::Dead;
+        hotel.apartments[1].is_opened = false;
+        hotel
+    }
+
+    #[test]
+    fn test_svg_colours_status_and_shades_locked_apartments() {
+        let svg = render_svg(&hotel_with_guest(), "#nsr", None);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("&lt;Bob&gt;"));
+        assert!(svg.contains(LEGEND[3].1)); // dead
+        assert!(svg.contains("fill=\"url(#locked)\" fill-opacity"));
+        for (label, _) in LEGEND {
+            assert!(svg.contains(&format!(">{}</text>", label)));
+        }
+    }
+
+    #[test]
+    fn test_html_is_standalone() {
+        let html = render(&hotel_with_guest(), ExportFormat::Html, "#nsr", None);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<svg"));
+        assert!(!html.contains("<script"));
+        assert_eq!(ExportFormat::parse("HTML"), Some(ExportFormat::Html));
+        assert_eq!(ExportFormat::parse("png"), None);
+    }
+}
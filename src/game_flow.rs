@@ -1,15 +1,23 @@
 use std::sync::{Arc, Mutex};
 
 use crate::{
+    bulletin_board::NoticeKind,
+    console,
     game_history::GameHistory,
     hotel::Hotel,
-    resident::{Resident, ResidentType},
+    locale, random,
+    replay::Recording,
+    report::{self, Report},
+    resident::{Resident, ResidentType, Status},
     roles::Role,
+    scope::{Scope, Viewer},
 };
-use rand::{distributions::Alphanumeric, seq::SliceRandom, thread_rng, Rng};
+use rand::{distributions::Alphanumeric, seq::SliceRandom, Rng};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
+const PIN_ATTEMPTS: usize = 3;
+
 pub struct GameFlow {
     pub hotel: Hotel,
     pub current_state: GameTime,
@@ -18,17 +26,41 @@ pub struct GameFlow {
     pub flow_sequence: FlowSequence,
     pub residents: Vec<Arc<Mutex<Resident>>>,
     pub game_history: GameHistory,
+    pub finished: bool, // someone has won and the report was shown
+    pub scope: Scope,
+    pub bots_only: bool, // every resident is a bot, even those added by hand
+    pub started: bool,   // `play` was given, so the game master's secret can't change anymore
+    game_master_secret: Option<String>,
+    snapshots: Vec<Snapshot>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Checkpoint {
+    Nightfall, // before the first move of a night
+    Turn,
+}
+
+/// Everything needed to play a turn again.
+struct Snapshot {
+    checkpoint: Checkpoint,
+    day: usize,
+    hotel: Hotel,
+    residents: Vec<usize>, // apartments in the order of moves
+    current_state: GameTime,
+    days_passed: usize,
+    current_moving_player: usize,
+    game_history: GameHistory,
 }
 
 impl GameFlow {
     pub fn new() -> Self {
-        let random_id: String = thread_rng()
+        let random_id: String = random::rng()
             .sample_iter(&Alphanumeric)
             .take(10)
             .map(char::from)
             .collect();
 
-        let hotel = Hotel::new(
+        let mut hotel = Hotel::new(
             random_id,
             16,
             10000.0,
@@ -38,6 +70,8 @@ impl GameFlow {
             1000.0,
             200.0,
         );
+        // the default hotel is built at once, so residents can settle in it without `hotel set`
+        hotel.reinitialize();
         Self {
             hotel,
             current_state: GameTime::Day,
@@ -46,10 +80,47 @@ impl GameFlow {
             flow_sequence: FlowSequence::Ordered,
             residents: Vec::new(),
             game_history: GameHistory::new(),
+            finished: false,
+            scope: Scope::default(),
+            bots_only: false,
+            started: false,
+            game_master_secret: None,
+            snapshots: vec![],
+        }
+    }
+
+    /// Starts the game, or resumes it after a pause. The secret that opens the game master's
+    /// view can only be chosen at the start.
+    pub fn start(&mut self, secret: Option<&str>) -> Result<(), String> {
+        if let Some(secret) = secret {
+            if self.started {
+                return Err(tr!("game.secret_fixed"));
+            }
+            self.game_master_secret = Some(secret.to_string());
+        }
+        self.started = true;
+        self.initialize();
+        Ok(())
+    }
+
+    /// The secret chosen at `play`, if any.
+    pub fn game_master_secret(&self) -> Option<&str> {
+        self.game_master_secret.as_deref()
+    }
+
+    /// Whether `secret` is the game master's, chosen when the game started. A game started
+    /// without one keeps no secrets: anyone may act as the game master.
+    pub fn check_game_master(&self, secret: Option<&str>) -> Result<(), String> {
+        match (&self.game_master_secret, secret) {
+            (None, _) => Ok(()),
+            (Some(expected), Some(secret)) if expected == secret => Ok(()),
+            _ => Err(tr!("scope.wrong_secret")),
         }
     }
 
     pub fn initialize(&mut self) {
+        self.snapshots.clear();
+        self.finished = false;
         self.residents = self.hotel.get_all_residents();
         match self.flow_sequence {
             FlowSequence::Alphabetical => {
@@ -80,7 +151,7 @@ impl GameFlow {
                 });
             }
             FlowSequence::Random => {
-                let mut rng = rand::thread_rng();
+                let mut rng = random::rng();
                 self.residents.shuffle(&mut rng);
             }
             FlowSequence::Chaotic => {
@@ -95,14 +166,44 @@ impl GameFlow {
 
     pub fn next_turn(&mut self) -> bool {
         // bool(next *human* turn made)
-        if self.current_moving_player == 0 {
+        if self.finished {
+            say!("{}", tr!("game.over"));
+            return true;
+        }
+        // the night falls with the first move of a day, whoever a cheat made move first
+        if matches!(self.current_state, GameTime::Day) {
+            self.take_snapshot(Checkpoint::Nightfall);
+            let first = (self.current_moving_player != 0)
+                .then(|| Arc::clone(&self.residents[self.current_moving_player]));
             self.switch_day_night();
+            // the chaotic flow reshuffles at nightfall, which mustn't take the turn away
+            if let Some(first) = first {
+                self.current_moving_player = self
+                    .residents
+                    .iter()
+                    .position(|resident| Arc::ptr_eq(resident, &first))
+                    .unwrap_or(0);
+            }
+        } else {
+            self.take_snapshot(Checkpoint::Turn);
         }
+        let handoff = self.needs_handoff();
         let is_human;
         {
             let mut resident = self.residents[self.current_moving_player].lock().unwrap();
             is_human = resident.resident_type == ResidentType::Human;
-            resident.perform_action(&mut self.hotel, &mut self.game_history);
+            if handoff {
+                // whoever takes the keyboard sees only what they know
+                self.scope = Scope::Player;
+            }
+            let takes_turn = !handoff || Self::hand_over(&resident);
+            if takes_turn {
+                resident.perform_action(&mut self.hotel, &mut self.game_history);
+            }
+            if handoff && takes_turn {
+                console::prompt(&tr!("handoff.done"));
+                console::clear();
+            }
         }
         self.current_moving_player = (self.current_moving_player + 1) % self.residents.len();
         if self.current_moving_player == 0 {
@@ -112,23 +213,228 @@ impl GameFlow {
         is_human
     }
 
+    /// A human playing at the host's keyboard gets the screen handed over if someone else
+    /// shares it, or if they guard their turn with a PIN.
+    fn needs_handoff(&self) -> bool {
+        if !self.hotel.house_rules.handoff {
+            return false;
+        }
+        let at_keyboard = |resident: &Resident| {
+            resident.resident_type == ResidentType::Human
+                && resident.status == Status::Alive
+                && !console::is_registered(resident.apartment_number)
+        };
+        {
+            let current = self.residents[self.current_moving_player].lock().unwrap();
+            if !at_keyboard(&current) {
+                return false;
+            }
+            if current.pin.is_some() {
+                return true;
+            }
+        }
+        let sharing = self
+            .residents
+            .iter()
+            .filter(|resident| at_keyboard(&resident.lock().unwrap()))
+            .count();
+        sharing > 1
+    }
+
+    /// Clears the screen and waits for `resident` to take the keyboard. `false` if they
+    /// couldn't give their PIN, and so miss the turn.
+    fn hand_over(resident: &Resident) -> bool {
+        console::clear();
+        console::prompt(&tr!(
+            "handoff.pass",
+            resident.name,
+            resident.apartment_number
+        ));
+        let Some(pin) = &resident.pin else {
+            return true;
+        };
+        for _ in 0..PIN_ATTEMPTS {
+            match console::prompt_secret(&tr!("handoff.pin")) {
+                Some(answer) if answer.trim() == pin => {
+                    console::clear();
+                    return true;
+                }
+                Some(_) => {}
+                None => break,
+            }
+            say!("{}", tr!("handoff.wrong_pin"));
+        }
+        console::clear();
+        say!(
+            "{}",
+            locale::plural("handoff.locked_out", PIN_ATTEMPTS, &[&resident.name])
+        );
+        false
+    }
+
+    /// Remembers the game before a move. Every nightfall is kept for replays and `rewind night`,
+    /// but single turns only for the night being played, and only if rewinding is allowed.
+    fn take_snapshot(&mut self, checkpoint: Checkpoint) {
+        match checkpoint {
+            Checkpoint::Nightfall => self
+                .snapshots
+                .retain(|snapshot| snapshot.checkpoint == Checkpoint::Nightfall),
+            Checkpoint::Turn if !self.hotel.house_rules.rewind => return,
+            Checkpoint::Turn => {}
+        }
+        let residents = self
+            .residents
+            .iter()
+            .map(|resident| resident.lock().unwrap().apartment_number)
+            .collect();
+        self.snapshots.push(Snapshot {
+            checkpoint,
+            day: self.game_history.day,
+            hotel: self.hotel.deep_clone(),
+            residents,
+            current_state: self.current_state,
+            days_passed: self.days_passed,
+            current_moving_player: self.current_moving_player,
+            game_history: self.game_history.clone(),
+        });
+    }
+
+    /// Goes back to the snapshot at `index`, forgetting it and everything after it.
+    fn restore(&mut self, index: usize) {
+        let snapshot = self.snapshots.drain(index..).next().unwrap();
+        let house_rules = self.hotel.house_rules;
+        self.hotel = snapshot.hotel;
+        self.hotel.house_rules = house_rules;
+        self.residents = snapshot
+            .residents
+            .iter()
+            .filter_map(|&apartment| self.hotel.apartments[apartment].resident.clone())
+            .collect();
+        self.current_state = snapshot.current_state;
+        self.days_passed = snapshot.days_passed;
+        self.current_moving_player = snapshot.current_moving_player;
+        self.game_history = snapshot.game_history;
+        self.finished = self.check_win_lose();
+    }
+
+    /// Takes back the last `turns` turns, or goes back to the night of `night`, if the house rules allow it.
+    pub fn take_back(&mut self, turns: usize, night: Option<usize>) -> Result<String, String> {
+        if !self.hotel.house_rules.rewind {
+            return Err(tr!("rewind.disabled"));
+        }
+        match night {
+            Some(day) => self.rewind_to_night(day)?,
+            None => self.rewind(turns)?,
+        }
+        let next = self
+            .residents
+            .get(self.current_moving_player)
+            .map(|resident| resident.lock().unwrap().name.clone())
+            .unwrap_or_default();
+        Ok(tr!("rewind.done", self.game_history.day, next))
+    }
+
+    /// Takes back the last `turns` turns.
+    pub fn rewind(&mut self, turns: usize) -> Result<(), String> {
+        if turns == 0 || turns > self.snapshots.len() {
+            return Err(tr!("rewind.turns", self.snapshots.len()));
+        }
+        self.restore(self.snapshots.len() - turns);
+        Ok(())
+    }
+
+    /// Goes back to the moment before the first move of the night of `day`.
+    pub fn rewind_to_night(&mut self, day: usize) -> Result<(), String> {
+        let index = self.snapshots.iter().position(|snapshot| {
+            snapshot.checkpoint == Checkpoint::Nightfall && snapshot.day == day
+        });
+        match index {
+            Some(index) => {
+                self.restore(index);
+                Ok(())
+            }
+            None => Err(tr!("rewind.no_night", day)),
+        }
+    }
+
+    /// The game so far: the hotel before the first night, and every night since.
+    pub fn record(&self) -> Recording {
+        let nightfalls: Vec<&Snapshot> = self
+            .snapshots
+            .iter()
+            .filter(|snapshot| snapshot.checkpoint == Checkpoint::Nightfall)
+            .collect();
+        let setup = nightfalls
+            .first()
+            .map(|snapshot| &snapshot.hotel)
+            .unwrap_or(&self.hotel);
+        // a night falls on the morning after the previous one
+        let mut dawns: Vec<(usize, &Hotel)> = nightfalls
+            .iter()
+            .skip(1)
+            .map(|snapshot| (snapshot.day - 1, &snapshot.hotel))
+            .collect();
+        let last_night = self.game_history.day - 1;
+        if self.current_moving_player == 0 && last_night > dawns.len() {
+            dawns.push((last_night, &self.hotel));
+        }
+        Recording::new(setup, &dawns, &self.game_history)
+    }
+
+    /// Whose knowledge the screen shows: `None` for the game master, otherwise the resident
+    /// whose turn it is.
+    pub fn viewer(&self) -> Option<Viewer> {
+        match self.scope {
+            Scope::GameMaster => None,
+            Scope::Player => Some(
+                self.residents
+                    .get(self.current_moving_player)
+                    .map(|resident| Viewer::of(&resident.lock().unwrap()))
+                    .unwrap_or_else(Viewer::nobody),
+            ),
+        }
+    }
+
+    /// Hidden roles may be shown to the game master, or to everyone once the game is over.
+    pub fn may_reveal(&self) -> bool {
+        self.scope == Scope::GameMaster || self.finished
+    }
+
+    /// Lets the bots move until it's the turn of a living human (at most one full round).
+    pub fn advance_to_human(&mut self) {
+        for _ in 0..self.residents.len() {
+            if self.finished || self.is_human_turn() {
+                break;
+            }
+            self.next_turn();
+        }
+    }
+
+    pub fn is_human_turn(&self) -> bool {
+        self.residents
+            .get(self.current_moving_player)
+            .map(|resident| {
+                let resident = resident.lock().unwrap();
+                resident.resident_type == ResidentType::Human && resident.status == Status::Alive
+            })
+            .unwrap_or(false)
+    }
+
     pub fn switch_day_night(&mut self) {
-        self.check_win_lose();
         match self.current_state {
             GameTime::Day => {
                 self.current_state = GameTime::Night;
-                match self.flow_sequence {
-                    FlowSequence::Chaotic => {
-                        let mut rng = rand::thread_rng();
-                        self.residents.shuffle(&mut rng);
-                    }
-                    _ => {}
+                if self.flow_sequence == FlowSequence::Chaotic {
+                    let mut rng = random::rng();
+                    self.residents.shuffle(&mut rng);
                 }
-                println!("It's night time!");
+                console::broadcast(&tr!("time.night"));
             }
             GameTime::Night => {
                 self.days_passed += 1;
                 self.current_state = GameTime::Day;
+                self.hotel.unlock_doors();
+                let arrested = self.hotel.resolve_investigations();
                 for resident in self.residents.iter() {
                     let mut resident = resident.lock().unwrap();
                     resident.update_state();
@@ -136,15 +442,49 @@ impl GameFlow {
                         .guests
                         .clear();
                 }
-                println!("It's day time!");
-                println!("{}", self.daily_announcement());
+                console::broadcast(&tr!("time.day"));
+                self.post_morning_notices(&arrested);
+                console::broadcast(&self.hotel.bulletin_board.render_day(self.game_history.day));
+                let announcement = self.daily_announcement();
+                if self.scope == Scope::GameMaster {
+                    say!("{}", announcement);
+                }
+                if !self.finished && self.check_win_lose() {
+                    self.finished = true;
+                    console::broadcast(&Report::new(&self.record(), &self.hotel).render_text());
+                }
             }
         }
     }
 
+    fn post_morning_notices(&mut self, arrested: &[usize]) {
+        let day = self.game_history.day;
+        let board = &mut self.hotel.bulletin_board;
+        for resident in self.residents.iter() {
+            let resident = resident.lock().unwrap();
+            let apartment = resident.apartment_number;
+            if resident.status == Status::Dead && !board.has_notice(NoticeKind::Death, apartment) {
+                board.notify(
+                    day,
+                    NoticeKind::Death,
+                    apartment,
+                    tr!("board.found_dead", resident.name, apartment),
+                );
+            }
+            if arrested.contains(&apartment) {
+                board.notify(
+                    day,
+                    NoticeKind::Arrest,
+                    apartment,
+                    tr!("board.arrested", resident.name, apartment),
+                );
+            }
+        }
+        board.viewed_day = day;
+    }
+
     pub fn check_win_lose(&self) -> bool {
-        // Check win/lose logic
-        false
+        report::winner(&self.hotel).is_some()
     }
 
     pub fn daily_announcement(&mut self) -> String {
@@ -155,7 +495,7 @@ impl GameFlow {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum GameTime {
     Day,
     Night,
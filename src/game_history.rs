@@ -1,19 +1,30 @@
-use crate::{hotel::Hotel, resident::Resident};
+use crate::{hotel::Hotel, locale, resident::Resident};
 use std::sync::MutexGuard;
 
-#[derive(Debug)]
+/// The actor of the changes made with `cheat`.
+pub const GAME_MASTER: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
 pub struct Action {
     pub day: usize,
     pub actor: usize,
     pub action_type: String,
     pub target: usize,
+    pub victim: Option<usize>, // who died of it: the target, one of their guests, or nobody
 }
 
+#[derive(Clone)]
 pub struct GameHistory {
     pub actions: Vec<Action>,
     pub day: usize,
 }
 
+impl Default for GameHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GameHistory {
     pub fn new() -> Self {
         Self {
@@ -35,18 +46,56 @@ impl GameHistory {
             actor,
             action_type,
             target,
+            victim: None,
         });
     }
 
+    /// Records an attack on `target` that killed `victim`, if anyone.
+    pub fn add_attack(
+        &mut self,
+        actor: usize,
+        action_type: String,
+        target: usize,
+        victim: Option<usize>,
+    ) {
+        self.add_action(actor, action_type, target, None);
+        self.actions.last_mut().unwrap().victim = victim;
+    }
+
+    /// The apartment `actor` went to tonight, if they left their own.
+    pub fn visit_of(&self, actor: usize) -> Option<usize> {
+        self.actions
+            .iter()
+            .find(|action| {
+                action.day == self.day && action.actor == actor && action.target != actor
+            })
+            .map(|action| action.target)
+    }
+
     pub fn retell_all_history(&self, hotel: &Hotel, format: Option<&str>) -> String {
-        self.retell_history(hotel, format, None)
+        self.retell_history(hotel, format, None, None)
     }
 
     pub fn retell_last_night(&self, hotel: &Hotel, format: Option<&str>) -> String {
         if self.actions.is_empty() {
-            return "Nothing happened?!?".to_string();
+            return tr!("history.nothing");
         }
-        self.retell_history(hotel, format, Some(self.day))
+        self.retell_history(hotel, format, Some(self.day), None)
+    }
+
+    pub fn retell_day(&self, hotel: &Hotel, format: Option<&str>, day: usize) -> String {
+        self.retell_history(hotel, format, Some(day), None)
+    }
+
+    /// Only the moves `actor` made on `day`, which is all a player may recall.
+    pub fn retell_own_day(
+        &self,
+        hotel: &Hotel,
+        format: Option<&str>,
+        day: usize,
+        actor: usize,
+    ) -> String {
+        self.retell_history(hotel, format, Some(day), Some(actor))
     }
 
     fn retell_history(
@@ -54,53 +103,65 @@ impl GameHistory {
         hotel: &Hotel,
         format: Option<&str>,
         day_filter: Option<usize>,
+        actor_filter: Option<usize>,
     ) -> String {
         let mut output = String::new();
         for action in &self.actions {
-            if let Some(day) = day_filter {
-                if action.day != day {
-                    continue;
-                }
+            if day_filter.is_some_and(|day| action.day != day)
+                || actor_filter.is_some_and(|actor| action.actor != actor)
+            {
+                continue;
             }
 
-            let actor = hotel.apartments[action.actor]
-                .resident
-                .as_ref()
-                .unwrap()
-                .lock()
-                .unwrap();
-
             let actor_format = format.unwrap_or("n (r)");
+            let actor_info = self.describe_apartment(hotel, action.actor, actor_format);
+            let target_info = self.describe_apartment(hotel, action.target, actor_format);
 
-            let actor_info: String = actor_format
-                .chars()
-                .map(|c| self.format_resident_detail(&actor, c))
-                .collect();
-
-            let target_info = if let Some(target) = &hotel.apartments[action.target].resident {
-                let target = &target.lock().unwrap();
-                actor_format
-                    .chars()
-                    .map(|c| self.format_resident_detail(target, c))
-                    .collect()
-            } else {
-                "None".to_string()
-            };
-
+            let action_type = locale::text_or(
+                &format!("action.{}", action.action_type),
+                &action.action_type,
+            );
             output.push_str(&format!(
-                "On day {}, {} {} {}\n",
-                action.day, actor_info, action.action_type, target_info
+                "{}\n",
+                tr!(
+                    "history.action",
+                    action.day,
+                    actor_info,
+                    action_type,
+                    target_info
+                )
             ));
         }
         output
     }
 
+    /// The resident of `apartment` in `format`, or "None" if nobody lives there.
+    fn describe_apartment(&self, hotel: &Hotel, apartment: usize, format: &str) -> String {
+        if apartment == GAME_MASTER {
+            return tr!("history.game_master");
+        }
+        match hotel
+            .apartments
+            .get(apartment)
+            .and_then(|apartment| apartment.resident.as_ref())
+        {
+            Some(resident) => {
+                let resident = resident.lock().unwrap();
+                format
+                    .chars()
+                    .map(|c| self.format_resident_detail(&resident, c))
+                    .collect()
+            }
+            None => tr!("history.nobody"),
+        }
+    }
+
     fn format_resident_detail(&self, resident: &MutexGuard<Resident>, param: char) -> String {
         match param {
             '#' => format!("{}", resident.apartment_number),
             '$' => format!("{:.2}", resident.account_balance),
             'a' => format!("{}", resident.age),
-            'n' => format!("{}", resident.name),
+            'n' => resident.name.to_string(),
             's' => format!("{:?}", resident.status),
             'r' => format!("{}", resident.strategy.confess_role()),
             't' => format!("{:?}", resident.resident_type),
@@ -118,3 +179,22 @@ impl GameHistory {
             .any(|action| action.actor == actor && action.target == target_apartment)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roles::Role;
+
+    #[test]
+    fn test_retell_tolerates_vacant_apartments() {
+        let hotel = Hotel::for_test(3, 3, &[("Dan", Role::Doctor)]);
+        let mut history = GameHistory::new();
+        history.add_action(2, "Kill".to_string(), 0, None);
+        history.add_action(0, "Heal".to_string(), 7, None);
+
+        assert_eq!(
+            history.retell_all_history(&hotel, None),
+            "On day 1, None Kill Dan (Doctor)\nOn day 1, Dan (Doctor) Heal None\n"
+        );
+    }
+}
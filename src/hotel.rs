@@ -1,21 +1,25 @@
 use rand::seq::SliceRandom;
-use rand::thread_rng;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, Read, Write};
-use std::path::Path;
+use std::io::{self, Write};
 use std::sync::{Arc, Mutex};
 use strum::IntoEnumIterator;
 
-use crate::mail::Suspicion;
-use crate::text_formatters::format_to_length;
-use crate::{apartment::Apartment, resident::Resident, roles::Role};
+use crate::bulletin_board::{BulletinBoard, NoticeKind};
+use crate::console;
+use crate::export::{self, ExportFormat};
+use crate::house_rules::HouseRules;
+use crate::locale::Locale;
+use crate::mail::{Mail, Suspicion};
+use crate::resident::{Status, SuperStatus};
+use crate::scope::Viewer;
+use crate::text_formatters::{display_width, pad_to_width, terminal_width, CellLayout};
+use crate::{apartment::Apartment, random, resident::Resident, roles::Role};
 
-const FORMAT_LENGTH_LEFT: usize = 2;
-const FORMAT_LENGTH_RIGHT: usize = 6;
-const APARTMENT_WIDTH: usize = 10;
+const ELEVATOR: &str = "|^v|";
+const DOOR: &str = "🚪";
 
 #[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum BuildingType {
@@ -26,7 +30,30 @@ pub enum BuildingType {
     Custom,
 }
 
-#[derive(Serialize, Deserialize)]
+impl BuildingType {
+    /// What the `building` command calls each type, in declaration order.
+    pub const NAMES: [&'static str; 5] = ["rectangular", "pyramidal", "random", "lax", "custom"];
+    const ALL: [BuildingType; 5] = [
+        BuildingType::Rectangular,
+        BuildingType::Pyramidal,
+        BuildingType::RandomShaped,
+        BuildingType::Lax,
+        BuildingType::Custom,
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .position(|known| known.eq_ignore_ascii_case(name))
+            .map(|index| Self::ALL[index])
+    }
+
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self as usize]
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Hotel {
     pub id: String,
     pub num_rooms: usize,
@@ -40,17 +67,22 @@ pub struct Hotel {
     pub apartments: Vec<Apartment>,
     #[serde(skip)]
     pub available_roles: Vec<Role>,
-    #[serde(skip)]
-    pub announcements: Vec<String>,
+    #[serde(default = "BulletinBoard::new")]
+    pub bulletin_board: BulletinBoard,
     #[serde(skip)]
     pub police_suspicions: Vec<Suspicion>,
     #[serde(skip)]
     pub investigation_queue: HashMap<usize, Suspicion>,
     #[serde(skip)]
     pub credible_sources: Vec<usize>,
+    #[serde(default)]
+    pub house_rules: HouseRules,
+    #[serde(default)]
+    pub language: Locale,
 }
 
 impl Hotel {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: String,
         num_rooms: usize,
@@ -72,18 +104,21 @@ impl Hotel {
             daily_costs,
             apartments: vec![],
             available_roles: vec![],
-            announcements: vec![],
+            bulletin_board: BulletinBoard::new(),
             police_suspicions: vec![],
             investigation_queue: HashMap::new(),
             credible_sources: vec![],
+            house_rules: HouseRules::default(),
+            language: Locale::default(),
         }
     }
 
-    pub fn get_ready_apartments(&self, own_apartment: Option<usize>) -> Vec<usize> {
+    /// Apartments one can visit: the open ones, and the locked ones they hold a key to.
+    pub fn get_ready_apartments(&self, own_apartment: Option<usize>, keys: &[usize]) -> Vec<usize> {
         self.apartments
             .iter()
             .filter_map(|apartment| {
-                if apartment.is_opened {
+                if !apartment.is_locked() || keys.contains(&apartment.number) {
                     if let Some(own_apartment) = own_apartment {
                         if apartment.number != own_apartment {
                             Some(apartment.number)
@@ -100,160 +135,336 @@ impl Hotel {
             .collect()
     }
 
+    /// Who is in `apartment` right now, by their own apartment numbers: its resident unless
+    /// they went out visiting, and the guests who came in. Only the living count.
+    pub fn present_in(&self, apartment: usize) -> Vec<usize> {
+        let Some(visited) = self.apartments.get(apartment) else {
+            return vec![];
+        };
+        let is_away = self
+            .apartments
+            .iter()
+            .any(|other| other.number != apartment && other.guests.contains(&apartment));
+        let mut present = vec![];
+        if visited.resident.is_some() && !is_away {
+            present.push(apartment);
+        }
+        present.extend(&visited.guests);
+        present.retain(|&home| {
+            self.apartments[home]
+                .resident
+                .as_ref()
+                .is_some_and(|resident| resident.lock().unwrap().status == Status::Alive)
+        });
+        present
+    }
+
+    /// Whoever a visitor to `apartment` runs into, by the apartment they live in. Every
+    /// action aimed at a resident reaches this person, not whoever lives there.
+    pub fn someone_in(&self, apartment: usize) -> Option<usize> {
+        self.present_in(apartment)
+            .choose(&mut random::rng())
+            .copied()
+    }
+
+    /// Kills someone who is in `apartment`; the apartment they live in, if anyone was there.
+    pub fn attack(&mut self, apartment: usize) -> Option<usize> {
+        let victim = self.someone_in(apartment)?;
+        if let Some(resident) = &self.apartments[victim].resident {
+            resident.lock().unwrap().status = Status::Dead;
+        }
+        Some(victim)
+    }
+
+    /// `visitor` walks into `target` and stays there until the morning. An Aggressive resident
+    /// wounds every newcomer, and an Aggressive visitor wounds everyone already there.
+    pub fn arrive(&mut self, visitor: &mut Resident, target: usize) {
+        let aggressive = visitor.super_status == SuperStatus::Aggressive;
+        for home in self.present_in(target) {
+            let Some(resident) = &self.apartments[home].resident else {
+                continue;
+            };
+            let mut resident = resident.lock().unwrap();
+            if resident.super_status == SuperStatus::Aggressive {
+                say!("{}", tr!("visit.wounded", visitor.apartment_number, home));
+                visitor.super_status = SuperStatus::Wounded;
+            }
+            if aggressive {
+                say!("{}", tr!("visit.wounded", home, visitor.apartment_number));
+                resident.super_status = SuperStatus::Wounded;
+            }
+        }
+        self.apartments[target]
+            .guests
+            .push(visitor.apartment_number);
+    }
+
+    /// Opens every door locked for the night; the ones the Janitor sealed stay locked.
+    pub fn unlock_doors(&mut self) {
+        for apartment in self.apartments.iter_mut() {
+            apartment.is_opened = true;
+        }
+    }
+
+    /// A copy that shares no residents with `self`, so that changing one doesn't touch the other.
+    pub fn deep_clone(&self) -> Self {
+        let mut hotel = self.clone();
+        for apartment in hotel.apartments.iter_mut() {
+            if let Some(resident) = &apartment.resident {
+                let resident = resident.lock().unwrap().clone();
+                apartment.resident = Some(Arc::new(Mutex::new(resident)));
+            }
+        }
+        hotel
+    }
+
     pub fn reinitialize(&mut self) {
         let possible_roles: Vec<Role> = Role::iter().collect();
         let roles_count = possible_roles.len();
         let mut available_roles = Vec::new();
         for i in 0..self.num_rooms {
-            available_roles.push(possible_roles[i % roles_count].clone());
+            available_roles.push(possible_roles[i % roles_count]);
         }
-        let mut rng = thread_rng();
+        let mut rng = random::rng();
         available_roles.shuffle(&mut rng);
 
         self.apartments = Hotel::initialize_apartments(self.num_rooms, self.rooms_per_story);
         self.available_roles = available_roles;
     }
 
-    pub fn print_hotel(&self, style: &str, destination: Option<usize>, player: Option<&Resident>) {
-        let re = Regex::new(r"^.{4}$").unwrap(); // any style
-                                                 // let re = Regex::new(r"^[#\$atsrnp]{4}$").unwrap();   // some specific info
-
-        if re.is_match(style) {
-            self.print_detailed(style);
-        } else {
-            match style {
-                "default" => self.print_detailed("#nsr"),
-                "move" => {
-                    if let (Some(dest), Some(player)) = (destination, player) {
-                        self.print_move(dest, player.apartment_number);
-                    } else {
-                        println!("Destination and player are required for 'move' style");
-                    }
+    pub fn print_hotel(
+        &self,
+        style: &str,
+        destination: Option<usize>,
+        player: Option<&Resident>,
+        viewer: Option<&Viewer>,
+    ) {
+        match style {
+            "default" => self.print_detailed("#nsr", viewer),
+            "move" => {
+                if let (Some(dest), Some(player)) = (destination, player) {
+                    self.print_move(dest, player.apartment_number);
+                } else {
+                    say!("{}", tr!("hotel.move_style"));
                 }
-                _ => println!("Invalid style"),
             }
+            style if Hotel::is_valid_style(style) => self.print_detailed(style, viewer),
+            _ => say!("{}", tr!("hotel.invalid_style")),
         }
     }
 
-    pub fn announce(&mut self) {
-        let mut announcement = String::new();
-        println!("Please, announce:");
-        io::stdin().read_line(&mut announcement).unwrap();
-        self.announcements.push(announcement);
+    pub fn announce(&mut self, author: Option<usize>, day: usize) {
+        let Some(announcement) = console::prompt(&tr!("hotel.announce")) else {
+            return;
+        };
+        self.bulletin_board.announce(day, author, announcement);
     }
 
-    pub fn send_mail(&mut self, apartment: usize, mail: String) {
-        self.apartments[apartment].mails.push(mail);
+    /// Arrests everyone the judges voted against the police's suspicion of, and forgets the
+    /// night's suspicions. Returns the apartments of the arrested.
+    pub fn resolve_investigations(&mut self) -> Vec<usize> {
+        let mut arrested = vec![];
+        for (target, suspicion) in self.investigation_queue.drain() {
+            if suspicion.for_votes <= suspicion.against_votes {
+                continue;
+            }
+            if let Some(resident) = &self.apartments[target].resident {
+                let mut resident = resident.lock().unwrap();
+                if resident.status == Status::Alive
+                    && resident.super_status != SuperStatus::Arrested
+                {
+                    resident.super_status = SuperStatus::Arrested;
+                    arrested.push(target);
+                }
+            }
+        }
+        arrested
     }
 
-    fn print_detailed(&self, custom_params: &str) {
-        let mut output = String::new();
+    pub fn send_mail(&mut self, mail: Mail) {
+        if mail.to >= self.apartments.len() {
+            say!("{}", tr!("hotel.invalid_apartment"));
+            return;
+        }
+        if let Some(sender) = self.apartments.get_mut(mail.from) {
+            sender.sent_mails.push(mail.clone());
+        }
+        self.apartments[mail.to].receive_mail(mail);
+    }
 
+    /// Apartment indices floor by floor, top floor first (`None` for unbuilt lots).
+    pub fn floor_plan(&self) -> Vec<Vec<Option<usize>>> {
+        if self.rooms_per_story == 0 {
+            return vec![];
+        }
         let total_floors = (self.num_rooms as f64 / self.rooms_per_story as f64).ceil() as usize;
 
-        for floor in (0..total_floors).rev() {
-            let mut line0 = String::new();
-            let mut line1 = String::new();
-            let mut line2 = String::new();
+        (0..total_floors)
+            .rev()
+            .map(|floor| {
+                (0..self.rooms_per_story)
+                    .map(|room| {
+                        let idx = floor * self.rooms_per_story + room;
+                        if idx < self.apartments.len() {
+                            Some(idx)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 
-            for room in 0..self.rooms_per_story {
-                let idx = floor * self.rooms_per_story + room;
+    fn print_detailed(&self, custom_params: &str, viewer: Option<&Viewer>) {
+        say!(
+            "{}",
+            self.render_detailed(custom_params, terminal_width(), viewer)
+        );
+    }
 
+    /// The floors as text no wider than `available` columns, one field per style character,
+    /// as `viewer` knows them (everything, for the game master).
+    pub fn render_detailed(
+        &self,
+        custom_params: &str,
+        available: usize,
+        viewer: Option<&Viewer>,
+    ) -> String {
+        let layout = CellLayout::new(custom_params.chars().map(Hotel::field_width).collect());
+        let layout = layout.fit(self.rooms_per_story, display_width(ELEVATOR), available);
+        let inner_width = layout.inner_width();
+        let mut output = String::new();
+
+        for floor in self.floor_plan() {
+            let mut lines = vec![String::new(); layout.lines() + 1];
+
+            for (room, idx) in floor.into_iter().enumerate() {
                 if room == self.elevator_position {
-                    line0.push_str("|^v|");
-                    line1.push_str("|^v|");
-                    line2.push_str("|^v|");
+                    for line in lines.iter_mut() {
+                        line.push_str(ELEVATOR);
+                    }
                 }
-                if idx >= self.apartments.len() {
-                    line0.push_str(&format!("|{:=^width$}|", "", width = APARTMENT_WIDTH));
-                    line1.push_str(&format!("|{: ^width$}|", "", width = APARTMENT_WIDTH));
-                    line2.push_str(&format!(
-                        "|{: ^width$}🚪 |",
-                        "",
-                        width = APARTMENT_WIDTH - 3
-                    ));
-                } else {
+                lines[0].push_str(&format!("|{:=^width$}|", "", width = inner_width));
+                if let Some(idx) = idx {
                     let details = custom_params
                         .chars()
-                        .map(|param| self.format_apartment_detail(&self.apartments[idx], param))
+                        .map(|param| {
+                            self.format_apartment_detail(&self.apartments[idx], param, viewer)
+                        })
                         .collect::<Vec<String>>();
-
-                    line0.push_str(&format!("|{:=^width$}|", "", width = APARTMENT_WIDTH));
-                    line1.push_str(&format!(
-                        "|{}: {}|",
-                        format_to_length(&details[0], FORMAT_LENGTH_LEFT),
-                        format_to_length(&details[1], FORMAT_LENGTH_RIGHT)
-                    ));
-                    line2.push_str(&format!(
-                        "|{}: {}|",
-                        format_to_length(&details[2], FORMAT_LENGTH_LEFT),
-                        format_to_length(&details[3], FORMAT_LENGTH_RIGHT)
-                    ));
+                    for (line, text) in lines.iter_mut().skip(1).enumerate() {
+                        text.push_str(&layout.render_line(line, &details));
+                    }
+                } else {
+                    let last = lines.len() - 1;
+                    for line in lines.iter_mut().take(last).skip(1) {
+                        line.push_str(&format!("|{}|", pad_to_width("", inner_width)));
+                    }
+                    let door = format!("{} ", DOOR);
+                    let padding = inner_width.saturating_sub(display_width(&door));
+                    lines[last].push_str(&format!("|{}{}|", " ".repeat(padding), door));
                 }
             }
 
-            output.push_str(&line0);
-            output.push('\n');
-            output.push_str(&line1);
-            output.push('\n');
-            output.push_str(&line2);
-            output.push('\n');
+            for line in lines {
+                output.push_str(&line);
+                output.push('\n');
+            }
         }
 
-        println!("{}", output);
+        output
     }
 
-    fn print_move(&self, destination: usize, position: usize) {
-        // todo: interactive position update
-        let mut output = String::new();
+    /// Columns a style character asks for before the layout is fitted to the terminal.
+    fn field_width(param: char) -> usize {
+        match param {
+            '#' => 2,
+            '$' => 8,
+            'a' => 3,
+            'n' => 10,
+            's' => 5,
+            'r' => 9,
+            't' => 5,
+            _ => 6,
+        }
+    }
 
-        let total_floors = (self.num_rooms as f64 / self.rooms_per_story as f64).ceil() as usize;
+    /// A style is up to eight of the fields `#$anrst`.
+    pub fn is_valid_style(style: &str) -> bool {
+        let re = Regex::new(r"^[#$anrst]{1,8}$").unwrap();
+        re.is_match(style)
+    }
 
-        for floor in (0..total_floors).rev() {
-            let mut line = String::new();
+    fn print_move(&self, destination: usize, position: usize) {
+        // todo: interactive position update
+        say!("{}", self.render_move(destination, position));
+    }
 
-            for room in 0..self.rooms_per_story {
-                let idx = floor * self.rooms_per_story + room;
+    /// The floors with the apartment numbers only, marking where the player is (`x`)
+    /// and where they are going (`+`).
+    fn render_move(&self, destination: usize, position: usize) -> String {
+        let digits = display_width(&self.num_rooms.saturating_sub(1).to_string()).max(2);
+        let layout = CellLayout::new(vec![digits + 2]);
+        let mut output = String::new();
 
+        for floor in self.floor_plan() {
+            for (room, idx) in floor.into_iter().enumerate() {
                 if room == self.elevator_position {
-                    line.push_str("| ^v |");
-                }
-                if idx >= self.apartments.len() {
-                    line.push_str("| 🚪 |");
-                } else {
-                    let symbol = if idx == destination {
-                        '+'
-                    } else if idx == position {
-                        'x'
-                    } else {
-                        'E'
-                    };
-                    line.push_str(&format!("|{:02} {}|", idx, symbol));
+                    output.push_str(ELEVATOR);
                 }
+                let text = match idx {
+                    Some(idx) if idx == destination => format!("{:0digits$} +", idx),
+                    Some(idx) if idx == position => format!("{:0digits$} x", idx),
+                    Some(idx) => format!("{:0digits$} E", idx),
+                    None => DOOR.to_string(),
+                };
+                output.push_str(&layout.render_line(0, &[text]));
             }
-
-            output.push_str(&line);
             output.push('\n');
         }
 
-        println!("{}", output);
+        output
     }
 
-    fn format_apartment_detail(&self, apartment: &Apartment, param: char) -> String {
+    /// One field of an apartment. Other people's money, true role and unreported deaths
+    /// are hidden from a `viewer`.
+    pub fn format_apartment_detail(
+        &self,
+        apartment: &Apartment,
+        param: char,
+        viewer: Option<&Viewer>,
+    ) -> String {
         if let Some(resident) = &apartment.resident {
             let resident = resident.lock().unwrap();
-            match param {
-                '#' => format!("{}", apartment.number),
-                '$' => format!("{:.2}", resident.account_balance),
-                'a' => format!("{}", resident.age),
-                'n' => format!("{}", resident.name),
-                's' => format!("{:?}", resident.status),
-                'r' => format!("{}", resident.strategy.confess_role()),
-                't' => format!("{:?}", resident.resident_type),
-                _ => format!("{} {:-^width$}", param, "", width = APARTMENT_WIDTH),
+            let stranger = viewer.filter(|viewer| !viewer.lives_in(apartment.number));
+            match (param, stranger) {
+                ('$', Some(_)) => "?".to_string(),
+                ('s', Some(_)) => {
+                    let reported = self
+                        .bulletin_board
+                        .has_notice(NoticeKind::Death, apartment.number);
+                    if reported {
+                        Status::Dead.to_string()
+                    } else {
+                        Status::Alive.to_string()
+                    }
+                }
+                ('r', Some(viewer)) => viewer
+                    .known_role(&resident.name)
+                    .map(|role| role.to_string())
+                    .unwrap_or("?".to_string()),
+                ('#', _) => format!("{}", apartment.number),
+                ('$', _) => format!("{:.2}", resident.account_balance),
+                ('a', _) => format!("{}", resident.age),
+                ('n', _) => resident.name.to_string(),
+                ('s', _) => resident.status.to_string(),
+                ('r', _) => format!("{}", resident.strategy.confess_role()),
+                ('t', _) => resident.resident_type.to_string(),
+                _ => format!("{} ---", param),
             }
         } else {
-            "Vacant".to_string()
+            tr!("hotel.vacant")
         }
     }
 
@@ -320,28 +531,207 @@ impl Hotel {
         if let Some(apartment) = self.apartments.get_mut(apartment_number) {
             apartment.assign_resident(resident);
         } else {
-            println!("Invalid apartment number.");
+            say!("{}", tr!("hotel.invalid_apartment"));
         }
     }
 
-    pub fn save(&self) -> io::Result<()> {
-        let path = format!("hotel_configs/{}.json", self.id);
+    /// The settings chosen while setting the hotel up, one per line.
+    pub fn describe_config(&self) -> String {
+        [
+            tr!("setup.id", self.id),
+            tr!("setup.rooms", self.num_rooms),
+            tr!("setup.rps", self.rooms_per_story),
+            tr!("setup.elevator", self.elevator_position),
+            tr!("setup.building", self.building_type.name()),
+            tr!("setup.capital", self.capital),
+            tr!("setup.fee", self.entrance_fee),
+            tr!("setup.service", self.daily_costs),
+            tr!("setup.language", self.language.code()),
+        ]
+        .join("\n")
+    }
+
+    pub fn export(
+        &self,
+        format: ExportFormat,
+        style: &str,
+        path: &str,
+        viewer: Option<&Viewer>,
+    ) -> io::Result<()> {
         let mut file = File::create(path)?;
-        let hotel_data = serde_json::to_string(self)?;
-        write!(file, "{}", hotel_data)?;
+        write!(file, "{}", export::render(self, format, style, viewer))?;
         Ok(())
     }
+}
+
+#[cfg(test)]
+impl Hotel {
+    /// A rectangular hotel ready to play, with elevator 1 and bots of the given
+    /// names and roles settled from apartment 0 on.
+    pub fn for_test(num_rooms: usize, rooms_per_story: usize, residents: &[(&str, Role)]) -> Self {
+        use crate::resident::{ResidentFactory, ResidentType};
+
+        let mut hotel = Hotel::new(
+            "test".to_string(),
+            num_rooms,
+            1000.0,
+            BuildingType::Rectangular,
+            1,
+            rooms_per_story,
+            10.0,
+            5.0,
+        );
+        hotel.reinitialize();
+        for (apartment, (name, role)) in residents.iter().enumerate() {
+            hotel.apartments[apartment].assign_resident(ResidentFactory::create_resident(
+                name.to_string(),
+                30,
+                100.0,
+                apartment,
+                *role,
+                ResidentType::Bot,
+            ));
+        }
+        hotel
+    }
+}
 
-    pub fn upload(id: &str) -> Option<Self> {
-        let path = format!("hotel_configs/{}.json", id);
-        if !Path::new(&path).exists() {
-            return None;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{Document, Face};
+    use crate::resident::{ResidentFactory, ResidentType};
+    use crate::text_formatters::display_width;
+
+    #[test]
+    fn test_render_detailed_keeps_columns_aligned() {
+        let hotel = Hotel::for_test(3, 2, &[("Zoë Łukasiewicz 🚪", Role::Killer)]);
+
+        for (style, available) in [("#nsr", 80), ("#n", 30), ("#nsr$at", 40)] {
+            let output = hotel.render_detailed(style, available, None);
+            let widths: Vec<usize> = output.lines().map(display_width).collect();
+            assert!(widths.iter().all(|&width| width == widths[0]));
+            assert!(widths[0] <= available);
         }
+        assert!(Hotel::is_valid_style("#n"));
+        assert!(!Hotel::is_valid_style(""));
+        assert!(!Hotel::is_valid_style("#x"));
+
+        let map = hotel.render_move(2, 0);
+        let widths: Vec<usize> = map.lines().map(display_width).collect();
+        assert!(widths.iter().all(|&width| width == widths[0]));
+        assert!(map.contains("|02 +|") && map.contains("|00 x|"));
+    }
+
+    #[test]
+    fn test_locked_doors_open_only_with_a_key() {
+        let mut hotel = Hotel::for_test(4, 2, &[]);
+        let mut resident = ResidentFactory::create_resident(
+            "Lee".to_string(),
+            30,
+            100.0,
+            2,
+            Role::Doctor,
+            ResidentType::Bot,
+        );
+        assert_eq!(resident.keys, vec![2]);
+
+        assert!(resident.lock_door(&mut hotel));
+        assert_eq!(hotel.get_ready_apartments(Some(0), &[]), vec![1, 3]);
+        assert_eq!(hotel.get_ready_apartments(Some(0), &[2]), vec![1, 2, 3]);
+
+        resident.keys.clear();
+        hotel.apartments[3].is_sealed = true;
+        hotel.unlock_doors();
+        assert!(!resident.lock_door(&mut hotel));
+        assert_eq!(hotel.get_ready_apartments(None, &[]), vec![0, 1, 2]);
+    }
 
-        let mut file = File::open(path).ok()?;
-        let mut hotel_data = String::new();
-        file.read_to_string(&mut hotel_data).ok()?;
-        let hotel: Hotel = serde_json::from_str(&hotel_data).ok()?;
-        Some(hotel)
+    #[test]
+    fn test_visitors_are_where_they_went() {
+        let mut hotel = Hotel::for_test(
+            3,
+            3,
+            &[
+                ("Kim", Role::Killer),
+                ("Ola", Role::OldLady),
+                ("Dan", Role::Doctor),
+            ],
+        );
+        let resident = |hotel: &Hotel, apartment: usize| {
+            let resident = hotel.apartments[apartment].resident.as_ref().unwrap();
+            let resident = resident.lock().unwrap().clone();
+            resident
+        };
+
+        let mut old_lady = resident(&hotel, 1);
+        hotel.arrive(&mut old_lady, 2);
+        assert_eq!(hotel.present_in(1), Vec::<usize>::new());
+        assert_eq!(hotel.present_in(2), vec![2, 1]);
+        assert_eq!(hotel.someone_in(1), None);
+        assert_eq!(hotel.attack(1), None);
+
+        hotel.apartments[2]
+            .resident
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .super_status = SuperStatus::Aggressive;
+        let mut killer = resident(&hotel, 0);
+        killer.super_status = SuperStatus::Aggressive;
+        hotel.arrive(&mut killer, 2);
+        assert_eq!(killer.super_status, SuperStatus::Wounded);
+        assert_eq!(resident(&hotel, 1).super_status, SuperStatus::Wounded);
+        assert_eq!(resident(&hotel, 2).super_status, SuperStatus::Wounded);
+    }
+
+    #[test]
+    fn test_player_view_shows_only_what_the_viewer_knows() {
+        let mut hotel = Hotel::for_test(
+            3,
+            3,
+            &[
+                ("Kim", Role::Killer),
+                ("Dan", Role::Doctor),
+                ("Pat", Role::Police),
+            ],
+        );
+        for apartment in &hotel.apartments {
+            apartment.resident.as_ref().unwrap().lock().unwrap().status = Status::Dead;
+        }
+        hotel
+            .bulletin_board
+            .notify(1, NoticeKind::Death, 2, "Pat was found dead".to_string());
+        let mut kim = hotel.apartments[0]
+            .resident
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .clone();
+        kim.documents.push(Document::new(
+            Role::Police,
+            "Pat".to_string(),
+            30,
+            Face::random(),
+        ));
+        let viewer = Viewer::of(&kim);
+        let detail = |apartment: usize, param| {
+            hotel.format_apartment_detail(&hotel.apartments[apartment], param, Some(&viewer))
+        };
+
+        assert_eq!(detail(0, 'r'), "Killer");
+        assert_eq!(detail(0, '$'), "100.00");
+        assert_eq!(detail(1, 'r'), "?");
+        assert_eq!(detail(1, '$'), "?");
+        assert_eq!(detail(1, 's'), "Alive");
+        assert_eq!(detail(1, 'n'), "Dan");
+        assert_eq!(detail(2, 'r'), "Police");
+        assert_eq!(detail(2, 's'), "Dead");
+        assert_eq!(
+            hotel.format_apartment_detail(&hotel.apartments[1], 'r', None),
+            "Doctor"
+        );
     }
 }
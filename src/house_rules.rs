@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Optional rules the host agrees on before the game starts.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HouseRules {
+    pub rewind: bool,  // `rewind` may take back turns; competitive games turn it off
+    pub handoff: bool, // players sharing a screen pass it on between their turns
+}
+
+impl Default for HouseRules {
+    fn default() -> Self {
+        Self {
+            rewind: true,
+            handoff: true,
+        }
+    }
+}
+
+impl HouseRules {
+    pub const NAMES: [&'static str; 2] = ["rewind", "handoff"];
+
+    pub fn get(&self, rule: &str) -> Option<bool> {
+        match rule {
+            "rewind" => Some(self.rewind),
+            "handoff" => Some(self.handoff),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, rule: &str, enabled: bool) -> bool {
+        match rule {
+            "rewind" => self.rewind = enabled,
+            "handoff" => self.handoff = enabled,
+            _ => return false,
+        }
+        true
+    }
+}
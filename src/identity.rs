@@ -0,0 +1,75 @@
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+
+use crate::{locale, random};
+
+/// How many personality traits a bot is born with.
+const TRAITS_PER_BOT: usize = 2;
+
+/// Every first and last name pair of the hotel's language.
+fn full_names() -> Vec<String> {
+    let last_names = locale::list("names.last");
+    locale::list("names.first")
+        .iter()
+        .flat_map(|first| {
+            last_names
+                .iter()
+                .map(move |last| format!("{} {}", first, last))
+        })
+        .collect()
+}
+
+/// A name nobody in `taken` has. Once every pair of names is taken, a number
+/// tells the namesakes apart, e.g. "Bob Smith 2".
+pub fn unique_name(taken: &[String]) -> String {
+    let mut rng = random::rng();
+    let taken: HashSet<&String> = taken.iter().collect();
+    let names = full_names();
+    let free: Vec<&String> = names.iter().filter(|name| !taken.contains(name)).collect();
+    if let Some(name) = free.choose(&mut rng) {
+        return name.to_string();
+    }
+    let name = names.choose(&mut rng).unwrap();
+    (2..)
+        .map(|number| format!("{} {}", name, number))
+        .find(|numbered| !taken.contains(numbered))
+        .unwrap()
+}
+
+/// A few different traits of character, as their documents describe them.
+pub fn traits() -> Vec<String> {
+    locale::list("traits")
+        .choose_multiple(&mut random::rng(), TRAITS_PER_BOT)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_stay_unique_when_the_pairs_run_out() {
+        let mut taken = vec![];
+        for _ in 0..100 {
+            taken.push(unique_name(&taken));
+        }
+        assert!(taken.iter().all(|name| name.split(' ').count() == 2));
+
+        let mut taken = full_names();
+        for _ in 0..3 {
+            taken.push(unique_name(&taken));
+        }
+        let numbered = &taken[taken.len() - 3..];
+        assert!(numbered.iter().all(|name| name.split(' ').count() == 3));
+
+        let mut distinct = taken.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), taken.len());
+
+        let traits = traits();
+        assert_eq!(traits.len(), TRAITS_PER_BOT);
+        assert_ne!(traits[0], traits[1]);
+    }
+}
@@ -0,0 +1,226 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Looks up a message of the catalogue in the current language, filling `{0}`, `{1}`, ...
+/// with the arguments, e.g. `tr!("night.kills", target)`.
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::locale::text($key, &[])
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::locale::text($key, &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+/// The languages the hotel speaks.
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "uk")]
+    Ukrainian,
+}
+
+type Catalogue = HashMap<String, Value>;
+
+fn load(json: &str) -> Catalogue {
+    serde_json::from_str(json).expect("the message catalogue is valid JSON")
+}
+
+lazy_static! {
+    static ref ENGLISH: Catalogue = load(include_str!("../locales/en.json"));
+    static ref UKRAINIAN: Catalogue = load(include_str!("../locales/uk.json"));
+}
+
+thread_local! {
+    static CURRENT: Cell<Locale> = const { Cell::new(Locale::English) };
+}
+
+impl Locale {
+    pub const NAMES: [&'static str; 2] = ["en", "uk"];
+
+    pub fn parse(code: &str) -> Option<Self> {
+        match code {
+            "en" => Some(Locale::English),
+            "uk" => Some(Locale::Ukrainian),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Ukrainian => "uk",
+        }
+    }
+
+    fn catalogue(self) -> &'static Catalogue {
+        match self {
+            Locale::English => &ENGLISH,
+            Locale::Ukrainian => &UKRAINIAN,
+        }
+    }
+
+    /// Which form a noun takes after `count`: English has "one" and "other",
+    /// Ukrainian "one" (21 ночі), "few" (22 ночі) and "many" (25 ночей).
+    pub fn plural_form(self, count: usize) -> &'static str {
+        match self {
+            Locale::English if count == 1 => "one",
+            Locale::English => "other",
+            Locale::Ukrainian => match (count % 10, count % 100) {
+                (1, 11) => "many",
+                (1, _) => "one",
+                (2..=4, 12..=14) => "many",
+                (2..=4, _) => "few",
+                _ => "many",
+            },
+        }
+    }
+}
+
+pub fn set(locale: Locale) {
+    CURRENT.with(|current| current.set(locale));
+}
+
+pub fn current() -> Locale {
+    CURRENT.with(Cell::get)
+}
+
+/// The entry for `key` in the current language, or in English if it isn't translated yet.
+fn lookup(key: &str) -> Option<(Locale, &'static Value)> {
+    let locale = current();
+    locale
+        .catalogue()
+        .get(key)
+        .map(|value| (locale, value))
+        .or_else(|| ENGLISH.get(key).map(|value| (Locale::English, value)))
+}
+
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    args.iter()
+        .enumerate()
+        .fold(template.to_string(), |text, (i, arg)| {
+            text.replace(&format!("{{{}}}", i), &arg.to_string())
+        })
+}
+
+/// A message, or its key if no language has it.
+pub fn text(key: &str, args: &[&dyn Display]) -> String {
+    match lookup(key) {
+        Some((_, Value::String(template))) => fill(template, args),
+        _ => key.to_string(),
+    }
+}
+
+/// The message of `key`, or `fallback` as it is, e.g. for actions logged as free text.
+pub fn text_or(key: &str, fallback: &str) -> String {
+    match lookup(key) {
+        Some((_, Value::String(message))) => message.clone(),
+        _ => fallback.to_string(),
+    }
+}
+
+/// A message that depends on a number, which fills `{0}`; the other arguments follow it.
+pub fn plural(key: &str, count: usize, args: &[&dyn Display]) -> String {
+    let Some((locale, Value::Object(forms))) = lookup(key) else {
+        return key.to_string();
+    };
+    let form = forms
+        .get(locale.plural_form(count))
+        .or_else(|| forms.get("other"))
+        .and_then(Value::as_str)
+        .unwrap_or(key);
+    let mut all: Vec<&dyn Display> = vec![&count];
+    all.extend_from_slice(args);
+    fill(form, &all)
+}
+
+/// A list of the catalogue, e.g. the titles a document of some role may carry.
+pub fn list(key: &str) -> Vec<String> {
+    match lookup(key) {
+        Some((_, Value::Array(items))) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manager::Manager, roles::Role};
+
+    #[test]
+    fn test_plural_forms() {
+        let forms: Vec<&str> = [1, 2, 5, 11, 12, 21, 22, 25, 111]
+            .iter()
+            .map(|count| Locale::Ukrainian.plural_form(*count))
+            .collect();
+        assert_eq!(
+            forms,
+            vec!["one", "few", "many", "many", "many", "one", "few", "many", "many"]
+        );
+        assert_eq!(plural("report.kills", 1, &[]), "1 kill");
+        assert_eq!(plural("report.kills", 3, &[]), "3 kills");
+        set(Locale::Ukrainian);
+        assert_eq!(plural("report.kills", 3, &[]), "3 вбивства");
+        assert_eq!(plural("report.kills", 5, &[]), "5 вбивств");
+    }
+
+    #[test]
+    fn test_every_message_is_translated() {
+        set(Locale::Ukrainian);
+        assert_eq!(Role::Doctor.to_string(), "Лікар");
+        assert_eq!(tr!("no.such.message"), "no.such.message");
+        for key in ENGLISH.keys() {
+            assert!(UKRAINIAN.contains_key(key), "{} is not translated", key);
+        }
+        set(Locale::English);
+        assert_eq!(Role::Doctor.to_string(), "Doctor");
+        assert_eq!(list("names.first").len(), 50);
+        assert_eq!(list("names.last").len(), 50);
+    }
+
+    #[test]
+    fn test_errors_and_states_speak_the_language() {
+        use crate::command::{ArgKind, CommandError};
+        use crate::resident::Status;
+
+        set(Locale::Ukrainian);
+        let error = CommandError::Invalid {
+            arg: "age",
+            expected: ArgKind::Age.expected(),
+        };
+        assert_eq!(error.to_string(), "age має бути віком від 1 до 120");
+        assert_eq!(Status::Dead.to_string(), "Мертвий");
+        set(Locale::English);
+        assert_eq!(CommandError::Missing("age").to_string(), "age is missing");
+        assert_eq!(Status::Dead.to_string(), "Dead");
+    }
+
+    #[test]
+    fn test_lang_command_switches_the_hotel() {
+        let mut manager = Manager::new();
+
+        manager.handle_command(&["lang", "uk"]);
+        manager.handle_command(&["lang", "xx"]);
+
+        let hotel = &manager.game_flow.hotel;
+        assert_eq!(
+            (hotel.language, current()),
+            (Locale::Ukrainian, Locale::Ukrainian)
+        );
+        assert_eq!(
+            hotel.bulletin_board.pinned[1],
+            "Коли западає ніч, лише один візит."
+        );
+        let json = serde_json::to_string(hotel).unwrap();
+        assert!(json.contains("\"language\":\"uk\""));
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt;
+
+#[derive(Clone)]
 pub struct Suspicion {
     pub from: usize,
     pub suspected: usize,
@@ -18,8 +21,65 @@ impl Suspicion {
     }
 }
 
+#[derive(Clone, Debug)]
 pub struct Mail {
     pub from: usize,
     pub to: usize,
     pub contents: String,
+    pub day: usize,
+    pub signed_as: Option<usize>, // forged sender, shown instead of the real one
+    pub is_anonymous: bool,
+    pub is_read: bool,
+}
+
+impl Mail {
+    pub fn new(from: usize, to: usize, contents: String, day: usize) -> Self {
+        Self {
+            from,
+            to,
+            contents: contents.trim().to_string(),
+            day,
+            signed_as: None,
+            is_anonymous: false,
+            is_read: false,
+        }
+    }
+
+    pub fn anonymous(mut self) -> Self {
+        self.is_anonymous = true;
+        self
+    }
+
+    pub fn forged(mut self, signed_as: usize) -> Self {
+        self.signed_as = Some(signed_as);
+        self
+    }
+
+    /// The sender the recipient gets to see (`None` for anonymous mails).
+    pub fn shown_sender(&self) -> Option<usize> {
+        if self.is_anonymous {
+            None
+        } else {
+            Some(self.signed_as.unwrap_or(self.from))
+        }
+    }
+}
+
+impl fmt::Display for Mail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sender = match self.shown_sender() {
+            Some(apartment) => tr!("mail.apartment", apartment),
+            None => tr!("mail.anonymous"),
+        };
+        let new = if self.is_read {
+            String::new()
+        } else {
+            tr!("mail.new")
+        };
+        write!(
+            f,
+            "{}",
+            tr!("mail.received", new, self.day, sender, self.contents)
+        )
+    }
 }
@@ -1,28 +1,61 @@
+#[macro_use]
+mod locale;
+#[macro_use]
+mod console;
+
 mod apartment;
+mod api;
+mod bulletin_board;
+mod cheat;
+mod cli;
+mod client;
+mod command;
+mod configs;
 mod debug;
 mod document;
+mod export;
 mod game_flow;
 pub mod game_history;
 mod hotel;
+mod house_rules;
+mod identity;
 pub mod mail;
 mod manager;
 mod manager_states;
+mod random;
+mod replay;
+mod report;
 mod resident;
 mod roles;
+mod roster;
+mod scope;
+mod script;
+mod server;
 pub mod strategies;
 pub mod text_formatters;
+mod tui;
+mod validation;
 
 use manager::Manager;
 use std::io::{self, Write};
 
-fn run() {
-    let mut manager = Manager::new();
+fn run(manager: &mut Manager) {
     loop {
         print!("    => ");
         io::stdout().flush().unwrap();
         let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
-        let input: Vec<&str> = input.trim().split_whitespace().collect();
+        match io::stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => break, // the input ended
+            Ok(_) => {}
+        }
+        let tokens = match command::tokenize(&input) {
+            Ok(tokens) => tokens,
+            Err(error) => {
+                println!("{}", error);
+                continue;
+            }
+        };
+        let input: Vec<&str> = tokens.iter().map(String::as_str).collect();
         if input.is_empty() {
             continue;
         }
@@ -35,5 +68,50 @@ fn run() {
 
 fn main() {
     // debug::debug();
-    run();
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() > 1 && args[1] == "connect" {
+        let address = args
+            .get(2)
+            .cloned()
+            .unwrap_or(format!("127.0.0.1:{}", server::DEFAULT_PORT));
+        if let Err(error) = client::connect(&address) {
+            println!("{}", tr!("server.no_connection", address, error));
+        }
+        return;
+    }
+    if args.len() > 1 && args[1] == "api" {
+        let port = args
+            .get(2)
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(api::DEFAULT_PORT);
+        let mut manager = Manager::new();
+        if let Err(error) = api::ApiServer::bind(port).and_then(|api| api.run(&mut manager)) {
+            println!("{}", tr!("api.server_error", error));
+        }
+        return;
+    }
+    let options = match cli::Options::parse(&args[1..]) {
+        Ok(options) => options,
+        Err(error) => {
+            println!("{}\n\n{}", error, cli::usage());
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", cli::usage());
+        return;
+    }
+    let mut manager = match options.start() {
+        Ok(manager) => manager,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let script_passed = options.run_script(&mut manager);
+    if options.stays_interactive() {
+        run(&mut manager);
+    } else if !script_passed {
+        std::process::exit(1);
+    }
 }
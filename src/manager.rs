@@ -1,10 +1,13 @@
 use crate::{
+    configs,
     game_flow::GameFlow,
     hotel::Hotel,
+    locale::{self, Locale},
     manager_states::{
         handling_result::HandlingResult, manager_state::ManagerState, GameState, PlayingState,
         SetUpHotelState, SettleResidentsState,
     },
+    script,
 };
 
 pub struct Manager {
@@ -24,6 +27,10 @@ impl Manager {
         }
     }
 
+    pub fn state(&self) -> &ManagerState {
+        &self.state
+    }
+
     pub fn empty_hotel(&mut self) {
         let prev_id = self.game_flow.hotel.id.clone();
         let prev_num_rooms = self.game_flow.hotel.num_rooms;
@@ -47,12 +54,68 @@ impl Manager {
     }
 
     pub(crate) fn handle_command(&mut self, input: &[&str]) {
+        locale::set(self.game_flow.hotel.language);
+        match input {
+            ["source", path] => {
+                match script::run_file(self, path) {
+                    Ok(()) => say!("{}", tr!("script.finished", path)),
+                    Err(error) => say!("{}", tr!("script.stopped", path, error)),
+                }
+                return;
+            }
+            ["expect", expectation @ ..] => {
+                match script::check(self, expectation) {
+                    Ok(()) => say!("{}", tr!("script.as_expected")),
+                    Err(message) => say!("{}", message),
+                }
+                return;
+            }
+            ["configs", rest @ ..] => {
+                match configs::run(rest) {
+                    Ok(message) | Err(message) => say!("{}", message.trim_end()),
+                }
+                return;
+            }
+            ["lang"] => {
+                say!(
+                    "{}",
+                    tr!(
+                        "lang.current",
+                        locale::current().code(),
+                        Locale::NAMES.join(", ")
+                    )
+                );
+                return;
+            }
+            ["lang", code] => {
+                match Locale::parse(code) {
+                    Some(language) => {
+                        let hotel = &mut self.game_flow.hotel;
+                        let default_rules =
+                            hotel.bulletin_board.pinned == locale::list("board.rules");
+                        hotel.language = language;
+                        locale::set(language);
+                        if default_rules {
+                            hotel.bulletin_board.pinned = locale::list("board.rules");
+                        }
+                        say!("{}", tr!("lang.set", code));
+                    }
+                    None => say!("{}", tr!("lang.unknown", code, Locale::NAMES.join(", "))),
+                }
+                return;
+            }
+            _ => {}
+        }
         let result = self.state.handle_command(&mut self.game_flow, input);
+        // a hotel loaded by the command may speak another language
+        locale::set(self.game_flow.hotel.language);
         match result {
             HandlingResult::KeepState => {}
             HandlingResult::ResetState => match self.state {
                 ManagerState::SetUpHotel(_) => {
+                    let bots_only = self.game_flow.bots_only;
                     self.game_flow = GameFlow::new();
+                    self.game_flow.bots_only = bots_only;
                     self.state = ManagerState::SetUpHotel(Box::new(SetUpHotelState));
                 }
                 ManagerState::SettleResidents(_) => {
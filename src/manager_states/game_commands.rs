@@ -0,0 +1,76 @@
+use std::fs;
+
+use crate::{
+    command::Command,
+    game_flow::GameFlow,
+    replay::{Recording, Replay},
+    report::Report,
+    scope::Scope,
+};
+
+/// Runs `view`, `record`, `replay` or `report`, which a paused and a running game share,
+/// and returns what to tell the host (empty once a replay has shown itself).
+pub fn handle(game_flow: &mut GameFlow, command: &Command) -> String {
+    match command.name {
+        "view" => {
+            match command.text("scope").and_then(Scope::parse) {
+                Some(Scope::GameMaster) => {
+                    if let Err(error) = game_flow.check_game_master(command.text("secret")) {
+                        return error;
+                    }
+                    game_flow.scope = Scope::GameMaster;
+                }
+                Some(scope) => game_flow.scope = scope,
+                None => {}
+            }
+            tr!(
+                "scope.shown_to",
+                tr!(&format!("scope.{:?}", game_flow.scope))
+            )
+        }
+        "record" => {
+            if !game_flow.may_reveal() {
+                return tr!("scope.game_master_only");
+            }
+            let path = command
+                .text("file")
+                .map(str::to_string)
+                .unwrap_or_else(|| Recording::path(&game_flow.hotel.id));
+            match game_flow.record().save(&path) {
+                Ok(()) => tr!("record.saved", path),
+                Err(error) => tr!("record.failed", error),
+            }
+        }
+        "replay" => {
+            // a recording, even one from a file, may be of this very game
+            if !game_flow.may_reveal() {
+                return tr!("scope.game_master_only");
+            }
+            let recording = match command.text("file") {
+                Some(path) => Recording::load(path),
+                None => Ok(game_flow.record()),
+            };
+            match recording {
+                Ok(recording) => {
+                    Replay::new(recording).run();
+                    String::new()
+                }
+                Err(error) => error,
+            }
+        }
+        "report" => {
+            if !game_flow.may_reveal() {
+                return tr!("scope.game_master_only");
+            }
+            let report = Report::new(&game_flow.record(), &game_flow.hotel);
+            match command.text("file") {
+                None => report.render_text(),
+                Some(path) => match fs::write(path, report.render_markdown()) {
+                    Ok(()) => tr!("report.saved", path),
+                    Err(error) => tr!("report.failed", error),
+                },
+            }
+        }
+        _ => unreachable!("only the shared game commands are handled here"),
+    }
+}
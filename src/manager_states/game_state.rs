@@ -1,7 +1,15 @@
 use strum::IntoEnumIterator;
 
-use super::{handling_result::HandlingResult, manager_state_behavior::ManagerStateBehavior};
+use super::{
+    game_commands, handling_result::HandlingResult, manager_state_behavior::ManagerStateBehavior,
+};
+use crate::command::{Arg, ArgKind, CommandRegistry, CommandSpec};
+use crate::configs;
+use crate::console;
 use crate::game_flow::{self, FlowSequence};
+use crate::house_rules::HouseRules;
+use crate::scope::Scope;
+use crate::server::{self, Server};
 
 #[derive(Clone)]
 pub struct GameState;
@@ -11,57 +19,217 @@ impl GameState {
         match fs {
             "ordered" => {
                 game_flow.flow_sequence = FlowSequence::Ordered;
-                println!("Sequence set to ordered.");
+                say!("{}", tr!("flow.set", tr!("flow.ordered")));
             }
             "random" => {
                 game_flow.flow_sequence = FlowSequence::Random;
-                println!("Sequence set to random.");
+                say!("{}", tr!("flow.set", tr!("flow.random")));
             }
             "chaotic" => {
                 game_flow.flow_sequence = FlowSequence::Chaotic;
-                println!("Sequence set to chaotic.");
+                say!("{}", tr!("flow.set", tr!("flow.chaotic")));
             }
             "alphabetical" => {
                 game_flow.flow_sequence = FlowSequence::Alphabetical;
-                println!("Sequence set to alphabetical.");
+                say!("{}", tr!("flow.set", tr!("flow.alphabetical")));
             }
             _ => {
-                println!("Incorrect option. You might've wanted to write: ");
-                let sequences: Vec<FlowSequence> = FlowSequence::iter().collect();
-                for si in 0..sequences.len() {
-                    print!("{}. {:?}", si, sequences[si]);
+                say!("{}", tr!("flow.incorrect"));
+                for (si, sequence) in FlowSequence::iter().enumerate() {
+                    say!("{}. {:?}", si, sequence);
                 }
             }
         }
     }
 }
 
+const COMMANDS: CommandRegistry = CommandRegistry::new(&[
+    CommandSpec {
+        name: "play",
+        args: &[Arg::optional("secret", ArgKind::Text)],
+        about: "about.game.play",
+    },
+    CommandSpec {
+        name: "flow",
+        args: &[Arg::required(
+            "sequence",
+            ArgKind::Choice(&["ordered", "random", "chaotic", "alphabetical"]),
+        )],
+        about: "about.game.flow",
+    },
+    CommandSpec {
+        name: "serve",
+        args: &[Arg::optional("port", ArgKind::Index)],
+        about: "about.game.serve",
+    },
+    CommandSpec {
+        name: "board",
+        args: &[],
+        about: "about.game.board",
+    },
+    CommandSpec {
+        name: "board pin",
+        args: &[Arg::required("rule", ArgKind::Rest)],
+        about: "about.game.board_pin",
+    },
+    CommandSpec {
+        name: "board unpin",
+        args: &[Arg::required("rule_number", ArgKind::Positive)],
+        about: "about.game.board_unpin",
+    },
+    CommandSpec {
+        name: "rules",
+        args: &[],
+        about: "about.game.rules",
+    },
+    CommandSpec {
+        name: "rule",
+        args: &[
+            Arg::required("rule", ArgKind::Choice(&HouseRules::NAMES)),
+            Arg::required("value", ArgKind::Choice(&["on", "off"])),
+        ],
+        about: "about.game.rule",
+    },
+    CommandSpec {
+        name: "rewind",
+        args: &[Arg::optional("turns", ArgKind::Positive)],
+        about: "about.game.rewind",
+    },
+    CommandSpec {
+        name: "rewind night",
+        args: &[Arg::required("day", ArgKind::Positive)],
+        about: "about.game.rewind_night",
+    },
+    CommandSpec {
+        name: "save",
+        args: &[],
+        about: "about.game.save",
+    },
+    CommandSpec {
+        name: "view",
+        args: &[
+            Arg::optional("scope", ArgKind::Choice(&Scope::NAMES)),
+            Arg::optional("secret", ArgKind::Text),
+        ],
+        about: "about.game.view",
+    },
+    CommandSpec {
+        name: "record",
+        args: &[Arg::optional("file", ArgKind::Text)],
+        about: "about.game.record",
+    },
+    CommandSpec {
+        name: "replay",
+        args: &[Arg::optional("file", ArgKind::Text)],
+        about: "about.game.replay",
+    },
+    CommandSpec {
+        name: "report",
+        args: &[Arg::optional("file", ArgKind::Text)],
+        about: "about.game.report",
+    },
+    CommandSpec {
+        name: "help",
+        args: &[Arg::optional("command", ArgKind::Rest)],
+        about: "about.game.help",
+    },
+]);
+
 impl ManagerStateBehavior for GameState {
     fn handle_command(
         &mut self,
         game_flow: &mut game_flow::GameFlow,
         input: &[&str],
     ) -> HandlingResult {
-        match input[0] {
-            "help" => {
-                println!("Available commands:");
-                println!("play -- changes the state into 'playing'");
-                println!(
-                    "save -- saves the progress to a file, using hotel ID as a unique identifier"
-                );
+        let command = match COMMANDS.parse(input) {
+            Ok(command) => command,
+            Err(error) => {
+                say!("{}", error);
+                return HandlingResult::KeepState;
+            }
+        };
+        match command.name {
+            "help" => console::write(&COMMANDS.help(command.text("command"))),
+            "board pin" => {
+                let rule = command.text("rule").unwrap().to_string();
+                game_flow.hotel.bulletin_board.pin(rule);
+                say!("{}", tr!("board.pinned"));
+            }
+            "board unpin" => {
+                let index = command.integer("rule_number").unwrap() - 1;
+                match game_flow.hotel.bulletin_board.unpin(index) {
+                    Some(rule) => say!("{}", tr!("board.unpinned", rule)),
+                    None => say!("{}", tr!("board.no_rule")),
+                }
+            }
+            "board" => {
+                for (i, rule) in game_flow.hotel.bulletin_board.pinned.iter().enumerate() {
+                    say!("{}. {}", i + 1, rule);
+                }
             }
-            "flow" if input.len() > 1 => {
-                self.set_game_flow_sequence(input[1], game_flow);
+            "flow" => {
+                self.set_game_flow_sequence(command.text("sequence").unwrap(), game_flow);
             }
             "play" => {
-                game_flow.initialize();
-                println!("Game started");
+                if let Err(error) = game_flow.start(command.text("secret")) {
+                    say!("{}", error);
+                    return HandlingResult::KeepState;
+                }
+                say!("{}", tr!("game.started"));
                 return HandlingResult::ChangeState;
             }
-            "save" => {
-                println!("Game progress saved for hotel ID: {}", game_flow.hotel.id);
+            "serve" => {
+                let port = match command.integer("port").map(u16::try_from) {
+                    None => server::DEFAULT_PORT,
+                    Some(Ok(port)) => port,
+                    Some(Err(_)) => {
+                        say!("{}", tr!("server.port", u16::MAX));
+                        return HandlingResult::KeepState;
+                    }
+                };
+                match Server::bind(port).and_then(|server| server.run(game_flow)) {
+                    Ok(_) => say!("{}", tr!("server.stopped")),
+                    Err(error) => say!("{}", tr!("server.error", error)),
+                }
+            }
+            "rules" => {
+                let rules = game_flow.hotel.house_rules;
+                for rule in HouseRules::NAMES {
+                    let value = if rules.get(rule).unwrap() {
+                        "on"
+                    } else {
+                        "off"
+                    };
+                    say!("{} -- {}", rule, value);
+                }
+            }
+            "rule" => {
+                if game_flow.started {
+                    say!("{}", tr!("rules.locked"));
+                    return HandlingResult::KeepState;
+                }
+                let rule = command.text("rule").unwrap();
+                let enabled = command.text("value") == Some("on");
+                game_flow.hotel.house_rules.set(rule, enabled);
+                say!("{}", tr!("rules.set", rule, command.text("value").unwrap()));
+            }
+            "rewind" | "rewind night" => {
+                let turns = command.integer("turns").unwrap_or(1);
+                match game_flow.take_back(turns, command.integer("day")) {
+                    Ok(message) | Err(message) => say!("{}", message),
+                }
+            }
+            "save" => match configs::save(&game_flow.hotel) {
+                Ok(_) => say!("{}", tr!("game.saved", game_flow.hotel.id)),
+                Err(_) => say!("{}", tr!("game.save_failed")),
+            },
+            "view" | "record" | "replay" | "report" => {
+                let text = game_commands::handle(game_flow, &command);
+                if !text.is_empty() {
+                    say!("{}", text);
+                }
             }
-            _ => println!("Invalid command"),
+            _ => unreachable!("every registered command is handled"),
         }
         HandlingResult::KeepState
     }
@@ -69,16 +237,21 @@ impl ManagerStateBehavior for GameState {
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpStream;
+    use std::thread;
+    use std::time::Duration;
 
     use super::*;
     use crate::game_flow::FlowSequence;
     use crate::manager::Manager;
     use crate::manager_states::manager_state::ManagerState;
+    use crate::resident::ResidentType;
 
     fn run_commands(manager: &mut Manager, commands: &[&str]) {
         for command in commands {
-            let input: Vec<&str> = command.trim().split_whitespace().collect();
+            let input: Vec<&str> = command.split_whitespace().collect();
             if !input.is_empty() {
                 manager.handle_command(&input);
             }
@@ -148,13 +321,107 @@ mod tests {
     fn test_save_command() {
         let mut manager = Manager::new_with_state(ManagerState::Game(Box::new(GameState)));
         let id = manager.game_flow.hotel.id.clone();
+        let directory = configs::use_temp_library("game-save");
 
         let commands = vec!["save"];
 
         run_commands(&mut manager, &commands);
 
-        let path = format!("hotel_configs/{}.json", id);
-        assert!(Path::new(&path).exists());
+        assert!(directory.join(format!("{}.json", id)).exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_the_board_is_kept_in_the_save() {
+        let mut manager = Manager::new_with_state(ManagerState::Game(Box::new(GameState)));
+        let id = manager.game_flow.hotel.id.clone();
+        let directory = configs::use_temp_library("board-save");
+        manager.game_flow.hotel.bulletin_board.announce(
+            1,
+            Some(2),
+            "Lost: one umbrella".to_string(),
+        );
+
+        run_commands(&mut manager, &["board pin No running in the halls", "save"]);
+
+        let saved = configs::load(&id).unwrap().unwrap().bulletin_board;
+        assert!(saved
+            .pinned
+            .contains(&"No running in the halls".to_string()));
+        assert_eq!(saved.notices_of_day(1)[0].text, "Lost: one umbrella");
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_board_pin_and_unpin_commands() {
+        let mut manager = Manager::new_with_state(ManagerState::Game(Box::new(GameState)));
+        let initial_rules = manager.game_flow.hotel.bulletin_board.pinned.len();
+        let commands = vec![
+            "board pin No running in the halls",
+            "board unpin 1",
+            "board",
+        ];
+
+        run_commands(&mut manager, &commands);
+
+        let pinned = &manager.game_flow.hotel.bulletin_board.pinned;
+        assert_eq!(pinned.len(), initial_rules);
+        assert_eq!(pinned.last().unwrap(), "No running in the halls");
+    }
+
+    #[test]
+    fn test_serve_routes_player_output_to_their_client() {
+        let mut manager = Manager::new();
+        let commands = vec![
+            "rooms 1",
+            "hotel set",
+            "add resident Alice 30 1000 0",
+            "residents settled",
+        ];
+        run_commands(&mut manager, &commands);
+
+        let server = Server::bind(0)
+            .unwrap()
+            .with_join_timeout(Duration::from_millis(200));
+        let address = server.local_addr().unwrap();
+        // connects first and never says a word; the lobby must drop it and go on
+        let silent = TcpStream::connect(address).unwrap();
+        let client = thread::spawn(move || {
+            let stream = TcpStream::connect(address).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut transcript = String::new();
+            let mut read_until = |text: &str, transcript: &mut String| loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    return;
+                }
+                transcript.push_str(&line);
+                if line.contains(text) {
+                    return;
+                }
+            };
+
+            writeln!(writer, "join 0").unwrap();
+            read_until("It's your turn", &mut transcript);
+            writeln!(writer, "whoami").unwrap();
+            read_until("Alice, 30 y.o.", &mut transcript);
+            writeln!(writer, "reveal").unwrap();
+            read_until("not available", &mut transcript);
+            writeln!(writer, "quit").unwrap();
+            read_until("", &mut transcript);
+            transcript
+        });
+
+        server.run(&mut manager.game_flow).unwrap();
+        let transcript = client.join().unwrap();
+        drop(silent);
+
+        assert!(transcript.contains("You are Alice from apartment 0"));
+        assert!(transcript.contains("Alice, 30 y.o., Human"));
+        assert!(transcript.contains("This command is not available to players."));
+        let resident = manager.game_flow.residents[0].lock().unwrap();
+        assert_eq!(resident.resident_type, ResidentType::Bot);
     }
 
     #[test]
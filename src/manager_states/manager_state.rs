@@ -10,6 +10,15 @@ pub enum ManagerState {
 }
 
 impl ManagerState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ManagerState::SetUpHotel(_) => "SetUpHotel",
+            ManagerState::SettleResidents(_) => "SettleResidents",
+            ManagerState::Game(_) => "Game",
+            ManagerState::Playing(_) => "Playing",
+        }
+    }
+
     pub fn handle_command(
         &mut self,
         game_flow: &mut game_flow::GameFlow,
@@ -1,6 +1,7 @@
 pub mod handling_result;
 pub mod manager_state_behavior;
 
+mod game_commands;
 pub mod game_state;
 pub mod manager_state;
 pub mod playing_state;
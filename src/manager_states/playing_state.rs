@@ -1,51 +1,352 @@
-use std::io;
-
-use super::{handling_result::HandlingResult, manager_state_behavior::ManagerStateBehavior};
-use crate::{game_flow, hotel::Hotel};
+use super::{
+    game_commands, handling_result::HandlingResult, manager_state_behavior::ManagerStateBehavior,
+};
+use crate::command::{Arg, ArgKind, CommandRegistry, CommandSpec};
+use crate::{
+    bulletin_board::NoticeKind, cheat, console, export::ExportFormat, game_flow, hotel::Hotel,
+    mail::Mail, scope::Scope, tui::Tui,
+};
 
 #[derive(Clone)]
 pub struct PlayingState;
 
 impl PlayingState {
-    fn print_hotel(&self, hotel: &Hotel, style: &str) {
-        hotel.print_hotel(style, None, None);
+    fn print_hotel(&self, game_flow: &game_flow::GameFlow, style: &str) {
+        let viewer = game_flow.viewer();
+        game_flow
+            .hotel
+            .print_hotel(style, None, None, viewer.as_ref());
+    }
+
+    fn export_hotel(
+        &self,
+        game_flow: &game_flow::GameFlow,
+        format: &str,
+        path: &str,
+        style: Option<&str>,
+    ) {
+        let format = ExportFormat::parse(format).unwrap();
+        let style = match style {
+            None | Some("default") => "#nsr",
+            Some(style) if Hotel::is_valid_style(style) => style,
+            Some(_) => {
+                say!("{}", tr!("hotel.invalid_style"));
+                return;
+            }
+        };
+        let viewer = game_flow.viewer();
+        match game_flow.hotel.export(format, style, path, viewer.as_ref()) {
+            Ok(()) => say!("{}", tr!("export.saved", path)),
+            Err(e) => say!("{}", tr!("export.failed", e)),
+        }
     }
 
-    fn mail(&self, game_flow: &mut game_flow::GameFlow) {
+    fn mail(&self, game_flow: &mut game_flow::GameFlow, anonymous: bool, signed_as: Option<usize>) {
         let resident = game_flow.residents[game_flow.current_moving_player]
             .lock()
             .unwrap();
+        if signed_as.is_some() && !resident.strategy.can_forge_mail() {
+            say!("{}", tr!("mail.cannot_forge"));
+            return;
+        }
+        if let Some(signed_as) = signed_as.filter(|&n| n >= game_flow.hotel.apartments.len()) {
+            say!("{}", tr!("mail.no_such_sender", signed_as));
+            return;
+        }
         let apartment = resident.apartment_number;
-        let target = resident
+        let Some(target) = resident
             .strategy
-            .choose_target(apartment, &mut game_flow.hotel);
-        let mut mail = String::new();
-        println!("Write your mail: ");
-        io::stdin().read_line(&mut mail).unwrap();
-        game_flow.hotel.send_mail(target, mail);
+            .choose_target(&resident, &mut game_flow.hotel)
+        else {
+            return;
+        };
+        let Some(contents) = console::prompt(&tr!("mail.write")) else {
+            say!("{}", tr!("input.ended"));
+            return;
+        };
+
+        let mut mail = Mail::new(apartment, target, contents, game_flow.game_history.day);
+        if anonymous {
+            mail = mail.anonymous();
+        }
+        if let Some(signed_as) = signed_as {
+            mail = mail.forged(signed_as);
+        }
+        game_flow.hotel.send_mail(mail);
+    }
+
+    fn board(&self, game_flow: &mut game_flow::GameFlow, page: Option<&str>) {
+        let last_day = game_flow.game_history.day;
+        let board = &mut game_flow.hotel.bulletin_board;
+        match page {
+            None => {}
+            Some("prev") => board.viewed_day = board.viewed_day.saturating_sub(1).max(1),
+            Some("next") => board.viewed_day = (board.viewed_day + 1).min(last_day),
+            Some(other) => {
+                if let Ok(day) = other.parse::<usize>() {
+                    if day == 0 || day > last_day {
+                        say!("{}", tr!("board.pages", last_day));
+                        return;
+                    }
+                    board.viewed_day = day;
+                } else if let Some(kind) = NoticeKind::parse(other) {
+                    for notice in board.notices_of_kind(kind) {
+                        say!("{}", tr!("board.notice", notice.day, notice));
+                    }
+                    return;
+                } else {
+                    say!("{}", tr!("board.usage"));
+                    return;
+                }
+            }
+        }
+        board.viewed_day = board.viewed_day.clamp(1, last_day);
+        say!("{}", board.render_day(board.viewed_day));
+    }
+
+    fn inbox(&self, game_flow: &mut game_flow::GameFlow, action: &str, number: Option<usize>) {
+        let address = game_flow.residents[game_flow.current_moving_player]
+            .lock()
+            .unwrap()
+            .apartment_number;
+        let apartment = &mut game_flow.hotel.apartments[address];
+        let index = number.map(|number| number - 1);
+
+        match (action, index) {
+            ("read", Some(index)) => match apartment.read_mail(index) {
+                Some(mail) => say!("{}", tr!("mail.numbered", index + 1, mail)),
+                None => say!("{}", tr!("mail.missing")),
+            },
+            ("delete", Some(index)) => match apartment.delete_mail(index) {
+                Some(_) => say!("{}", tr!("mail.deleted", index + 1)),
+                None => say!("{}", tr!("mail.missing")),
+            },
+            _ => apartment.print_inbox(),
+        }
     }
 }
 
+const COMMANDS: CommandRegistry = CommandRegistry::new(&[
+    CommandSpec {
+        name: "move",
+        args: &[],
+        about: "about.playing.move",
+    },
+    CommandSpec {
+        name: "hotel",
+        args: &[Arg::required("style", ArgKind::Text)],
+        about: "about.playing.hotel",
+    },
+    CommandSpec {
+        name: "hotel export",
+        args: &[
+            Arg::required("format", ArgKind::Choice(&["svg", "html"])),
+            Arg::required("file", ArgKind::Text),
+            Arg::optional("style", ArgKind::Text),
+        ],
+        about: "about.playing.hotel_export",
+    },
+    CommandSpec {
+        name: "mail",
+        args: &[],
+        about: "about.playing.mail",
+    },
+    CommandSpec {
+        name: "mail anonymous",
+        args: &[],
+        about: "about.playing.mail_anonymous",
+    },
+    CommandSpec {
+        name: "mail as",
+        args: &[Arg::required("apartment", ArgKind::Index)],
+        about: "about.playing.mail_as",
+    },
+    CommandSpec {
+        name: "inbox",
+        args: &[],
+        about: "about.playing.inbox",
+    },
+    CommandSpec {
+        name: "inbox read",
+        args: &[Arg::required("mail_number", ArgKind::Positive)],
+        about: "about.playing.inbox_read",
+    },
+    CommandSpec {
+        name: "inbox delete",
+        args: &[Arg::required("mail_number", ArgKind::Positive)],
+        about: "about.playing.inbox_delete",
+    },
+    CommandSpec {
+        name: "sent",
+        args: &[],
+        about: "about.playing.sent",
+    },
+    CommandSpec {
+        name: "clear",
+        args: &[],
+        about: "about.playing.clear",
+    },
+    CommandSpec {
+        name: "lock",
+        args: &[],
+        about: "about.playing.lock",
+    },
+    CommandSpec {
+        name: "announce",
+        args: &[],
+        about: "about.playing.announce",
+    },
+    CommandSpec {
+        name: "announce anonymous",
+        args: &[],
+        about: "about.playing.announce_anonymous",
+    },
+    CommandSpec {
+        name: "board",
+        args: &[Arg::optional("page", ArgKind::Text)],
+        about: "about.playing.board",
+    },
+    CommandSpec {
+        name: "rewind",
+        args: &[Arg::optional("turns", ArgKind::Positive)],
+        about: "about.playing.rewind",
+    },
+    CommandSpec {
+        name: "rewind night",
+        args: &[Arg::required("day", ArgKind::Positive)],
+        about: "about.game.rewind_night",
+    },
+    CommandSpec {
+        name: "tui",
+        args: &[],
+        about: "about.playing.tui",
+    },
+    CommandSpec {
+        name: "cheat",
+        args: &[Arg::optional("secret_and_tool", ArgKind::Rest)],
+        about: "about.playing.cheat",
+    },
+    CommandSpec {
+        name: "pause",
+        args: &[],
+        about: "about.playing.pause",
+    },
+    CommandSpec {
+        name: "reveal",
+        args: &[],
+        about: "about.playing.reveal",
+    },
+    CommandSpec {
+        name: "whoami",
+        args: &[],
+        about: "about.playing.whoami",
+    },
+    CommandSpec {
+        name: "restart",
+        args: &[],
+        about: "about.playing.restart",
+    },
+    CommandSpec {
+        name: "new game",
+        args: &[],
+        about: "about.playing.new_game",
+    },
+    CommandSpec {
+        name: "view",
+        args: &[
+            Arg::optional("scope", ArgKind::Choice(&Scope::NAMES)),
+            Arg::optional("secret", ArgKind::Text),
+        ],
+        about: "about.game.view",
+    },
+    CommandSpec {
+        name: "record",
+        args: &[Arg::optional("file", ArgKind::Text)],
+        about: "about.game.record",
+    },
+    CommandSpec {
+        name: "replay",
+        args: &[Arg::optional("file", ArgKind::Text)],
+        about: "about.game.replay",
+    },
+    CommandSpec {
+        name: "report",
+        args: &[Arg::optional("file", ArgKind::Text)],
+        about: "about.game.report",
+    },
+    CommandSpec {
+        name: "help",
+        args: &[Arg::optional("command", ArgKind::Rest)],
+        about: "about.game.help",
+    },
+]);
+
 impl ManagerStateBehavior for PlayingState {
     fn handle_command(
         &mut self,
         game_flow: &mut game_flow::GameFlow,
         input: &[&str],
     ) -> HandlingResult {
-        match input[0] {
+        let command = match COMMANDS.parse(input) {
+            Ok(command) => command,
+            Err(error) => {
+                say!("{}", error);
+                return HandlingResult::KeepState;
+            }
+        };
+        match command.name {
             "move" => {
                 while !game_flow.next_turn() {
                     // the first human's move will trigger the loop to teminate
                 }
             }
-            "hotel" if input.len() > 1 => {
-                self.print_hotel(&game_flow.hotel, input[1]);
+            "hotel export" => {
+                self.export_hotel(
+                    game_flow,
+                    command.text("format").unwrap(),
+                    command.text("file").unwrap(),
+                    command.text("style"),
+                );
+            }
+            "hotel" => {
+                self.print_hotel(game_flow, command.text("style").unwrap());
             }
             "mail" => {
-                self.mail(game_flow);
+                self.mail(game_flow, false, None);
+            }
+            "mail anonymous" => {
+                self.mail(game_flow, true, None);
+            }
+            "mail as" => {
+                self.mail(game_flow, false, command.integer("apartment"));
+            }
+            "inbox" => {
+                self.inbox(game_flow, "list", None);
+            }
+            "inbox read" | "inbox delete" => {
+                let action = command.name.trim_start_matches("inbox ");
+                self.inbox(game_flow, action, command.integer("mail_number"));
+            }
+            "sent" => {
+                let player_number = game_flow.current_moving_player;
+                let address = game_flow.residents[player_number]
+                    .lock()
+                    .unwrap()
+                    .apartment_number;
+                game_flow.hotel.apartments[address].print_sent_mails();
+            }
+            "announce" | "announce anonymous" => {
+                let author = if command.name == "announce anonymous" {
+                    None
+                } else {
+                    let player_number = game_flow.current_moving_player;
+                    let resident = game_flow.residents[player_number].lock().unwrap();
+                    Some(resident.apartment_number)
+                };
+                game_flow.hotel.announce(author, game_flow.game_history.day);
             }
-            "announce" => {
-                game_flow.hotel.announce();
+            "board" => {
+                self.board(game_flow, command.text("page"));
             }
             "clear" => {
                 let player_number = game_flow.current_moving_player;
@@ -54,45 +355,72 @@ impl ManagerStateBehavior for PlayingState {
                     .unwrap()
                     .apartment_number;
                 game_flow.hotel.apartments[address].clear_mails();
-                println!("Your mails were cleared.");
+                say!("{}", tr!("mail.cleared"));
             }
-            "cheat" => {
-                // Implement cheat logic
-                println!("Cheat used");
+            "lock" => {
+                if matches!(game_flow.current_state, game_flow::GameTime::Day) {
+                    say!("{}", tr!("door.daytime"));
+                    return HandlingResult::KeepState;
+                }
+                let resident = game_flow.residents[game_flow.current_moving_player]
+                    .lock()
+                    .unwrap();
+                let address = resident.apartment_number;
+                if resident.lock_door(&mut game_flow.hotel) {
+                    say!("{}", tr!("door.locked", address));
+                } else {
+                    say!("{}", tr!("door.no_key", address));
+                }
+            }
+            "rewind" | "rewind night" => {
+                let turns = command.integer("turns").unwrap_or(1);
+                match game_flow.take_back(turns, command.integer("day")) {
+                    Ok(message) | Err(message) => say!("{}", message),
+                }
             }
+            "tui" => match Tui::new().run(game_flow) {
+                Ok(result) => return result,
+                Err(e) => say!("{}", tr!("tui.failed", e)),
+            },
+            "cheat" => match cheat::run(game_flow, &input[1..]) {
+                Ok(message) | Err(message) => say!("{}", message.trim_end()),
+            },
             "pause" => {
-                println!("Game paused");
+                say!("{}", tr!("game.paused"));
                 return HandlingResult::ChangeState;
             }
             "reveal" => {
+                if !game_flow.may_reveal() {
+                    say!("{}", tr!("scope.game_master_only"));
+                    return HandlingResult::KeepState;
+                }
                 for resident in &game_flow.residents {
                     let resident = resident.lock().unwrap();
-                    println!("{}", resident);
+                    say!("{}", resident);
                 }
             }
             "whoami" => {
                 let resident = game_flow.residents[game_flow.current_moving_player]
                     .lock()
                     .unwrap();
-                println!("{}", resident);
+                say!("{}", resident);
             }
             "restart" => {
-                println!("Game restarted");
+                say!("{}", tr!("game.restarted"));
                 return HandlingResult::Restart;
             }
-            "new" if input.len() > 1 && input[1] == "game" => {
-                println!("New game started");
+            "new game" => {
+                say!("{}", tr!("game.new"));
                 return HandlingResult::ResetState;
             }
-            "help" => {
-                println!("Available commands:");
-                println!("move -- depends on the strategy of the player");
-                println!("cheat -- allows to do something unallowed");
-                println!("pause -- changes state back to 'game'");
-                println!("restart -- restarts the current game");
-                println!("new -- starts a new game");
+            "help" => console::write(&COMMANDS.help(command.text("command"))),
+            "view" | "record" | "replay" | "report" => {
+                let text = game_commands::handle(game_flow, &command);
+                if !text.is_empty() {
+                    say!("{}", text);
+                }
             }
-            _ => println!("Invalid command"),
+            _ => unreachable!("every registered command is handled"),
         }
         HandlingResult::KeepState
     }
@@ -100,13 +428,18 @@ impl ManagerStateBehavior for PlayingState {
 
 #[cfg(test)]
 mod tests {
-    use crate::{manager::Manager, manager_states::manager_state::ManagerState};
+    use std::sync::Arc;
+
+    use crate::{
+        manager::Manager, manager_states::manager_state::ManagerState, replay::Event,
+        resident::Status,
+    };
 
     use super::*;
 
     fn run_commands(manager: &mut Manager, commands: &[&str]) {
         for command in commands {
-            let input: Vec<&str> = command.trim().split_whitespace().collect();
+            let input: Vec<&str> = command.split_whitespace().collect();
             if !input.is_empty() {
                 manager.handle_command(&input);
             }
@@ -147,6 +480,131 @@ mod tests {
         assert_eq!(manager.game_flow.hotel.apartments[address].mails.len(), 0);
     }
 
+    #[test]
+    fn test_inbox_read_and_delete_commands() {
+        let mut manager = Manager::new();
+        let commands = vec!["hotel set", "residents settled", "play"];
+        run_commands(&mut manager, &commands);
+
+        let player_number = manager.game_flow.current_moving_player;
+        let address = manager.game_flow.residents[player_number]
+            .lock()
+            .unwrap()
+            .apartment_number;
+        let sender = (address + 1) % manager.game_flow.hotel.apartments.len();
+        manager
+            .game_flow
+            .hotel
+            .send_mail(Mail::new(sender, address, "Hello".to_string(), 1));
+        manager
+            .game_flow
+            .hotel
+            .send_mail(Mail::new(sender, address, "Bye".to_string(), 1).anonymous());
+
+        run_commands(&mut manager, &["inbox read 2", "inbox delete 1"]);
+
+        let apartment = &manager.game_flow.hotel.apartments[address];
+        assert_eq!(apartment.mails.len(), 1);
+        assert_eq!(apartment.mails[0].contents, "Bye");
+        assert!(apartment.mails[0].is_read);
+        assert_eq!(apartment.mails[0].shown_sender(), None);
+        assert_eq!(
+            manager.game_flow.hotel.apartments[sender].sent_mails.len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_forged_mail_shows_fake_sender() {
+        let mut manager = Manager::new();
+        let commands = vec!["hotel set"];
+        run_commands(&mut manager, &commands);
+
+        let mail = Mail::new(0, 1, "Meet me at midnight".to_string(), 1).forged(5);
+        manager.game_flow.hotel.send_mail(mail);
+        manager
+            .game_flow
+            .hotel
+            .send_mail(Mail::new(0, 999, "Lost".to_string(), 1));
+
+        let inbox = &manager.game_flow.hotel.apartments[1].mails;
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].from, 0);
+        assert_eq!(inbox[0].shown_sender(), Some(5));
+        assert_eq!(manager.game_flow.hotel.apartments[0].sent_mails.len(), 1);
+    }
+
+    #[test]
+    fn test_morning_board_reports_deaths() {
+        let mut manager = Manager::new();
+        let commands = vec!["hotel set", "residents settled", "play"];
+        run_commands(&mut manager, &commands);
+
+        let victim = manager.game_flow.residents[0].clone();
+        let apartment = {
+            let mut victim = victim.lock().unwrap();
+            victim.status = Status::Dead;
+            victim.apartment_number
+        };
+        manager.game_flow.switch_day_night();
+        manager.game_flow.switch_day_night();
+        run_commands(&mut manager, &["board 1", "board deaths"]);
+
+        let board = &manager.game_flow.hotel.bulletin_board;
+        assert!(board.has_notice(NoticeKind::Death, apartment));
+        assert_eq!(board.notices_of_day(1).len(), 1);
+        assert_eq!(board.viewed_day, 1);
+    }
+
+    #[test]
+    fn test_judges_arrest_the_suspect_of_the_police() {
+        use crate::{
+            document::Document,
+            game_flow::GameTime,
+            hotel::Hotel,
+            resident::{ResidentType, SuperStatus},
+            roles::Role,
+        };
+
+        let mut manager = Manager::new();
+        let mut hotel = Hotel::for_test(
+            4,
+            2,
+            &[
+                ("Pat", Role::Police),
+                ("Dan", Role::Doctor),
+                ("Jo", Role::Judge),
+            ],
+        );
+        hotel.house_rules.handoff = false;
+        for apartment in [0, 2] {
+            let resident = hotel.apartments[apartment].resident.as_ref().unwrap();
+            resident.lock().unwrap().resident_type = ResidentType::Human;
+        }
+        let suspect = hotel.apartments[1].resident.clone().unwrap();
+        {
+            let mut suspect = suspect.lock().unwrap();
+            let face = suspect.face;
+            suspect.documents = vec![Document::new(Role::Killer, "Kim".to_string(), 30, face)];
+        }
+        manager.game_flow.hotel = hotel;
+        manager.game_flow.start(None).unwrap();
+
+        // the police investigate apartment 1, the judge votes for the arrest
+        let host = console::BufferChannel::new(["1", "+"].map(String::from).to_vec());
+        let previous = console::set_host(Some(Box::new(host)));
+        for _ in 0..3 {
+            manager.game_flow.next_turn();
+        }
+        console::set_host(previous);
+
+        assert!(matches!(manager.game_flow.current_state, GameTime::Day));
+        assert_eq!(suspect.lock().unwrap().super_status, SuperStatus::Arrested);
+        let board = &manager.game_flow.hotel.bulletin_board;
+        assert!(board.has_notice(NoticeKind::Arrest, 1));
+        assert!(manager.game_flow.hotel.investigation_queue.is_empty());
+    }
+
     #[test]
     fn test_cheat_command() {
         let mut manager = Manager::new_with_state(ManagerState::Playing(Box::new(PlayingState)));
@@ -208,4 +666,204 @@ mod tests {
         run_commands(&mut manager, &commands);
         // This is a print test and will require manual checking of the output.
     }
+
+    #[test]
+    fn test_doors_are_locked_only_at_night() {
+        let mut manager = Manager::new();
+        run_commands(
+            &mut manager,
+            &["hotel set", "residents settled", "play", "lock"],
+        );
+        assert!(manager
+            .game_flow
+            .hotel
+            .apartments
+            .iter()
+            .all(|apartment| !apartment.is_locked()));
+
+        manager.game_flow.switch_day_night();
+        run_commands(&mut manager, &["lock"]);
+        let address = manager.game_flow.residents[manager.game_flow.current_moving_player]
+            .lock()
+            .unwrap()
+            .apartment_number;
+        assert!(manager.game_flow.hotel.apartments[address].is_locked());
+    }
+
+    #[test]
+    fn test_rewind_night_restores_residents_and_history() {
+        let mut manager = Manager::new();
+        run_commands(&mut manager, &["hotel set", "residents settled", "play"]);
+        let statuses = |manager: &Manager| -> Vec<(String, Status)> {
+            let residents = manager.game_flow.hotel.get_all_residents();
+            let residents = residents.iter().map(|resident| resident.lock().unwrap());
+            residents.map(|r| (r.name.clone(), r.status)).collect()
+        };
+        let before = statuses(&manager);
+
+        run_commands(&mut manager, &["move", "move", "rewind night 1"]);
+
+        assert_eq!(manager.game_flow.game_history.day, 1);
+        assert!(manager.game_flow.game_history.actions.is_empty());
+        assert_eq!(manager.game_flow.current_moving_player, 0);
+        assert_eq!(statuses(&manager), before);
+        let in_hotel = manager.game_flow.hotel.get_all_residents();
+        for resident in &manager.game_flow.residents {
+            assert!(in_hotel.iter().any(|r| Arc::ptr_eq(r, resident)));
+        }
+
+        run_commands(&mut manager, &["move", "rewind 3"]);
+        assert_eq!(manager.game_flow.current_moving_player, 13);
+    }
+
+    #[test]
+    fn test_rewind_is_gated_by_house_rule() {
+        let mut manager = Manager::new();
+        run_commands(
+            &mut manager,
+            &["hotel set", "residents settled", "rule rewind off", "play"],
+        );
+
+        run_commands(&mut manager, &["move", "rewind night 1"]);
+        assert_eq!(manager.game_flow.game_history.day, 2);
+
+        run_commands(&mut manager, &["pause", "rule rewind on", "play"]);
+        assert!(!manager.game_flow.hotel.house_rules.rewind);
+    }
+
+    #[test]
+    fn test_record_keeps_the_setup_and_every_dawn() {
+        let mut manager = Manager::new();
+        run_commands(&mut manager, &["hotel set", "residents settled", "play"]);
+        let residents = manager.game_flow.hotel.get_all_residents().len();
+
+        run_commands(&mut manager, &["move", "move"]);
+        let recording = manager.game_flow.record();
+        // both nightfalls are kept, but only the turns of the last night
+        assert!(manager.game_flow.rewind(residents + 2).is_err());
+        assert!(manager.game_flow.rewind(residents + 1).is_ok());
+
+        assert_eq!(recording.nights(), 2);
+        assert_eq!(recording.setup.residents.len(), residents);
+        assert!(recording
+            .setup
+            .residents
+            .iter()
+            .all(|resident| resident.status == Status::Alive));
+        let dawns: Vec<usize> = recording
+            .events
+            .iter()
+            .filter(|event| matches!(event, Event::Dawn { .. }))
+            .map(Event::day)
+            .collect();
+        assert_eq!(dawns, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_nobody_moves_once_the_game_is_over() {
+        let mut manager = Manager::new();
+        run_commands(&mut manager, &["hotel set", "residents settled", "play"]);
+        manager.game_flow.finished = true;
+
+        run_commands(&mut manager, &["move"]);
+
+        assert!(manager.game_flow.game_history.actions.is_empty());
+        assert_eq!(manager.game_flow.current_moving_player, 0);
+    }
+
+    #[test]
+    fn test_roles_are_revealed_to_the_game_master_only() {
+        let mut manager = Manager::new();
+        run_commands(
+            &mut manager,
+            &["hotel set", "residents settled", "play s3cret"],
+        );
+        let host = console::BufferChannel::new(vec![]);
+        let output = host.output();
+        let previous = console::set_host(Some(Box::new(host)));
+
+        run_commands(
+            &mut manager,
+            &[
+                "reveal",
+                "report",
+                "replay inputs/none.json",
+                "view gm",
+                "view gm guess",
+            ],
+        );
+        let hidden = output.borrow().clone();
+        run_commands(&mut manager, &["view gm s3cret", "reveal"]);
+        let revealed = output.borrow().clone();
+        run_commands(&mut manager, &["pause", "play other"]);
+        console::set_host(previous);
+
+        assert_eq!(hidden.matches("Only the game master").count(), 3);
+        assert_eq!(hidden.matches("not the game master's secret").count(), 2);
+        assert!(!hidden.contains("shown to: GameMaster"));
+        assert_eq!(manager.state().name(), "Game");
+        assert!(manager.game_flow.check_game_master(Some("s3cret")).is_ok());
+        assert!(revealed.contains("The screen is shown to: GameMaster"));
+        assert!(revealed.contains("Account balance"));
+    }
+
+    #[test]
+    fn test_a_game_without_a_secret_has_an_open_game_master() {
+        let mut manager = Manager::new();
+        run_commands(&mut manager, &["hotel set", "residents settled", "play"]);
+        let host = console::BufferChannel::new(vec![]);
+        let output = host.output();
+        let previous = console::set_host(Some(Box::new(host)));
+
+        run_commands(&mut manager, &["view gm", "cheat give 0 5"]);
+        console::set_host(previous);
+
+        assert_eq!(manager.game_flow.scope, Scope::GameMaster);
+        assert!(output
+            .borrow()
+            .contains("The game master gives 5.00 to apartment 0."));
+    }
+
+    #[test]
+    fn test_hot_seat_hands_over_and_checks_the_pin() {
+        let mut manager = Manager::new();
+        run_commands(
+            &mut manager,
+            &[
+                "hotel set",
+                "add resident Alice 30 100 1 1234",
+                "add resident Bob 30 100 2",
+                "residents settled",
+                "play",
+            ],
+        );
+        let alice = manager
+            .game_flow
+            .residents
+            .iter()
+            .position(|resident| resident.lock().unwrap().name == "Alice")
+            .unwrap();
+        manager.game_flow.current_moving_player = alice;
+        manager.game_flow.scope = Scope::GameMaster;
+        let answers = ["", "4321", "1111", "0000"].map(String::from).to_vec();
+        let host = console::BufferChannel::new(answers);
+        let output = host.output();
+        let previous = console::set_host(Some(Box::new(host)));
+
+        manager.game_flow.next_turn();
+        console::set_host(previous);
+
+        let output = output.borrow();
+        assert!(output.contains("Pass to Alice (apartment 1), press Enter"));
+        assert_eq!(output.matches("Wrong PIN.").count(), 3);
+        assert!(output.contains("Alice misses the turn"));
+        assert!(!output.contains("Your turn is over"));
+        assert_eq!(manager.game_flow.scope, Scope::Player);
+        assert!(!manager
+            .game_flow
+            .game_history
+            .actions
+            .iter()
+            .any(|action| action.actor == 1));
+    }
 }
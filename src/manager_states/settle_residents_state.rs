@@ -1,10 +1,18 @@
 use super::{handling_result::HandlingResult, manager_state_behavior::ManagerStateBehavior};
-use crate::{game_flow, hotel::Hotel, resident::ResidentFactory};
+use crate::{
+    command::{Arg, ArgKind, CommandRegistry, CommandSpec},
+    console, game_flow,
+    hotel::Hotel,
+    locale,
+    resident::{ResidentFactory, ResidentType},
+    roster,
+};
 
 #[derive(Clone)]
 pub struct SettleResidentsState;
 
 impl SettleResidentsState {
+    #[allow(clippy::too_many_arguments)]
     pub fn add_resident(
         &self,
         hotel: &mut Hotel,
@@ -12,33 +20,35 @@ impl SettleResidentsState {
         age: usize,
         account_balance: f64,
         apartment_number: Option<usize>,
+        pin: Option<String>,
+        resident_type: ResidentType,
     ) {
         if hotel.available_rooms_count() == 0 {
-            println!("No rooms available");
+            say!("{}", tr!("settle.no_rooms"));
             return;
         }
 
         if let Some(apartment_number) = apartment_number {
             if hotel.is_room_available(apartment_number) {
                 if let Some(role) = hotel.random_available_role() {
-                    let resident = ResidentFactory::create_resident(
+                    let mut resident = ResidentFactory::create_resident(
                         name,
                         age,
                         account_balance,
                         apartment_number,
                         role,
-                        crate::resident::ResidentType::Human,
+                        resident_type,
                     );
+                    resident.pin = pin;
                     hotel.add_resident(resident, apartment_number);
                 } else {
-                    println!("No roles available");
+                    say!("{}", tr!("settle.no_roles"));
                 }
             } else {
-                println!("Room unavailable");
+                say!("{}", tr!("settle.room_taken"));
             }
         } else {
-            println!("No apartment number provided");
-            return;
+            say!("{}", tr!("settle.no_apartment"));
         }
     }
 
@@ -46,60 +56,126 @@ impl SettleResidentsState {
         while hotel.available_rooms_count() > 0 {
             if let Some(next_available_room) = hotel.find_next_available_room() {
                 if let Some(role) = hotel.random_available_role() {
-                    let bot = ResidentFactory::generate_random(next_available_room, role);
+                    let taken: Vec<String> = hotel
+                        .get_all_residents()
+                        .iter()
+                        .map(|resident| resident.lock().unwrap().name.clone())
+                        .collect();
+                    let bot = ResidentFactory::generate_random(next_available_room, role, &taken);
                     hotel.add_resident(bot, next_available_room);
                 }
             } else {
                 break;
             }
         }
-        println!("Remaining rooms settled with bots");
+        say!("{}", tr!("settle.bots"));
     }
 }
 
+const COMMANDS: CommandRegistry = CommandRegistry::new(&[
+    CommandSpec {
+        name: "add resident",
+        args: &[
+            Arg::required("name", ArgKind::Text),
+            Arg::required("age", ArgKind::Age),
+            Arg::required("account_balance", ArgKind::Number),
+            Arg::required("apartment", ArgKind::Index),
+            Arg::optional("pin", ArgKind::Text),
+        ],
+        about: "about.settle.add_resident",
+    },
+    CommandSpec {
+        name: "import residents",
+        args: &[Arg::required("file", ArgKind::Rest)],
+        about: "about.settle.import_residents",
+    },
+    CommandSpec {
+        name: "get room",
+        args: &[Arg::required("apartment", ArgKind::Index)],
+        about: "about.settle.get_room",
+    },
+    CommandSpec {
+        name: "available",
+        args: &[],
+        about: "about.settle.available",
+    },
+    CommandSpec {
+        name: "residents settled",
+        args: &[],
+        about: "about.settle.residents_settled",
+    },
+    CommandSpec {
+        name: "help",
+        args: &[Arg::optional("command", ArgKind::Rest)],
+        about: "about.setup.help",
+    },
+]);
+
 impl ManagerStateBehavior for SettleResidentsState {
     fn handle_command(
         &mut self,
         game_flow: &mut game_flow::GameFlow,
         input: &[&str],
     ) -> HandlingResult {
-        match input[0] {
-            "add" if input.len() == 6 && input[1] == "resident" => {
-                let name = input[2].to_string();
-                let age: usize = input[3].parse().unwrap_or(0);
-                let account_balance: f64 = input[4].parse().unwrap_or(0.0);
-                let apartment_number: usize = input[5].parse().unwrap_or(0);
+        let command = match COMMANDS.parse(input) {
+            Ok(command) => command,
+            Err(error) => {
+                say!("{}", error);
+                return HandlingResult::KeepState;
+            }
+        };
+        let resident_type = if game_flow.bots_only {
+            ResidentType::Bot
+        } else {
+            ResidentType::Human
+        };
+        match command.name {
+            "add resident" => {
                 self.add_resident(
                     &mut game_flow.hotel,
-                    name,
-                    age,
-                    account_balance,
-                    Some(apartment_number),
+                    command.text("name").unwrap().to_string(),
+                    command.integer("age").unwrap(),
+                    command.number("account_balance").unwrap(),
+                    command.integer("apartment"),
+                    command.text("pin").map(str::to_string),
+                    resident_type,
                 );
             }
-            "available" => {
-                println!("Available rooms: {:?}", game_flow.hotel.available_rooms());
-            }
-            "get" if input.len() == 3 && input[1] == "room" => {
-                let apartment_number: usize = input[2].parse().unwrap_or(0);
-                match game_flow.hotel.get_room(apartment_number) {
-                    Some((number, floor)) => println!("Room {} on floor {}", number, floor),
-                    None => println!("Apartment not found"),
+            "import residents" => {
+                let path = command.text("file").unwrap();
+                match roster::import(&mut game_flow.hotel, path, resident_type) {
+                    Ok(count) => say!("{}", locale::plural("roster.imported", count, &[&path])),
+                    Err(errors) => {
+                        for error in errors {
+                            say!("{}", error);
+                        }
+                        say!("{}", tr!("roster.rejected"));
+                    }
                 }
             }
-            "residents" if input.len() == 2 && input[1] == "settled" => {
+            "available" => {
+                say!(
+                    "{}",
+                    tr!(
+                        "settle.available",
+                        format!("{:?}", game_flow.hotel.available_rooms())
+                    )
+                );
+            }
+            "get room" => match game_flow
+                .hotel
+                .get_room(command.integer("apartment").unwrap())
+            {
+                Some((number, floor)) => say!("{}", tr!("settle.room", number, floor)),
+                None => say!("{}", tr!("settle.not_found")),
+            },
+            "residents settled" => {
                 self.settle_remaining_residents(&mut game_flow.hotel);
-                println!("Residents settled. Moving to game stage.");
+                say!("{}", tr!("settle.done"));
                 return HandlingResult::ChangeState;
             }
-            "help" => {
-                println!("Available commands:");
-                println!("add resident [name] [age] [account balance] [apartment] -- to add a resident to the hotel");
-                println!("get room [apartment number] -- to get the floor and room number of the apartment");
-                println!("available -- to get the list of available rooms");
-                println!("residents settled -- to move on to the next stage");
-            }
-            _ => println!("Invalid command. Try 'help' instead."),
+            "help" => console::write(&COMMANDS.help(command.text("command"))),
+            _ => unreachable!("every registered command is handled"),
         }
         HandlingResult::KeepState
     }
@@ -109,13 +185,14 @@ impl ManagerStateBehavior for SettleResidentsState {
 mod tests {
     use super::*;
     use crate::{
+        command,
         manager::Manager,
         manager_states::{manager_state::ManagerState, SetUpHotelState},
     };
 
     fn run_commands(manager: &mut Manager, commands: &[&str]) {
         for command in commands {
-            let input: Vec<&str> = command.trim().split_whitespace().collect();
+            let input: Vec<&str> = command.split_whitespace().collect();
             if !input.is_empty() {
                 manager.handle_command(&input);
             }
@@ -147,6 +224,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_add_resident_with_quoted_name_and_named_arguments() {
+        let mut manager = Manager::new_with_state(ManagerState::SettleResidents(Box::new(
+            SettleResidentsState,
+        )));
+        manager.game_flow.hotel.num_rooms = 10;
+        manager.game_flow.hotel.reinitialize();
+
+        for line in [
+            r#"add resident "Alice Johnson" 30 1000 1"#,
+            r#"add resident apartment=2 age=41 account_balance=500 name='Bob Li'"#,
+            r#"add resident Carol -5 100 3"#,
+        ] {
+            let tokens = command::tokenize(line).unwrap();
+            let input: Vec<&str> = tokens.iter().map(String::as_str).collect();
+            manager.handle_command(&input);
+        }
+
+        let name = |apartment: usize| {
+            let resident = manager.game_flow.hotel.apartments[apartment]
+                .resident
+                .as_ref();
+            resident.map(|resident| resident.lock().unwrap().name.clone())
+        };
+        assert_eq!(name(1).as_deref(), Some("Alice Johnson"));
+        assert_eq!(name(2).as_deref(), Some("Bob Li"));
+        assert_eq!(name(3), None); // age must be a positive integer
+    }
+
     #[test]
     fn test_add_resident_with_unavailable_room() {
         let mut manager = Manager::new_with_state(ManagerState::SettleResidents(Box::new(
@@ -214,6 +320,17 @@ mod tests {
 
         assert!(initial_num_rooms > 0);
         assert_eq!(manager.game_flow.hotel.available_rooms_count(), 0);
+        let residents = manager.game_flow.hotel.get_all_residents();
+        let mut names: Vec<String> = residents
+            .iter()
+            .map(|resident| resident.lock().unwrap().name.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), initial_num_rooms);
+        let bot = residents[0].lock().unwrap();
+        assert!(!bot.traits.is_empty());
+        assert!(bot.documents[0].to_string().contains(&bot.traits[0]));
     }
 
     #[test]
@@ -1,116 +1,209 @@
-use crate::game_flow;
-use crate::hotel::Hotel;
+use crate::hotel::{BuildingType, Hotel};
+use crate::validation::{self, Setting};
+use crate::{configs, console, game_flow};
 
 use super::handling_result::HandlingResult;
 use super::manager_state_behavior::ManagerStateBehavior;
+use crate::command::{Arg, ArgKind, CommandRegistry, CommandSpec, Value};
 
 #[derive(Clone)]
 pub struct SetUpHotelState;
 
 impl SetUpHotelState {
     fn print_hotel_config(&self, hotel: &Hotel) {
-        println!("Current hotel configuration:");
-        println!("ID: {}", hotel.id);
-        println!("Number of rooms: {}", hotel.num_rooms);
-        println!("Rooms per story: {}", hotel.rooms_per_story);
-        println!("Initial capital: {}", hotel.capital);
-        println!("Entrance fee: {}", hotel.entrance_fee);
-        println!("Daily service cost: {}", hotel.daily_costs);
+        say!("{}", tr!("setup.config"));
+        say!("{}", hotel.describe_config());
     }
 
     fn set_hotel_id(&mut self, game_flow: &mut game_flow::GameFlow, id: String) {
-        match Hotel::upload(&id) {
-            Some(hotel) => {
-                println!("Hotel found with ID: {}", hotel.id);
+        match configs::load(&id) {
+            Ok(Some(hotel)) => {
+                say!("{}", tr!("setup.found", hotel.id));
                 game_flow.hotel = hotel;
             }
-            None => {
-                println!("Hotel ID set to: {}", id);
+            Ok(None) => {
+                say!("{}", tr!("setup.id_set", id));
                 game_flow.hotel.id = id;
             }
+            Err(error) => say!("{}", error),
+        }
+    }
+
+    /// Sets one setting from a command, telling what is still wrong with the others.
+    fn change(&self, hotel: &mut Hotel, setting: Setting, value: &Value) {
+        match validation::set(hotel, setting, value) {
+            Ok(warnings) => {
+                let key = format!("setup.{}_set", setting.command());
+                say!("{}", tr!(&key, setting.current(hotel)));
+                for warning in warnings {
+                    say!("{}", tr!("setup.warning", warning));
+                }
+            }
+            Err(error) => say!("{}", error),
+        }
+    }
+
+    /// Asks for every setting in turn; Enter keeps the value shown in brackets.
+    fn run_wizard(&self, hotel: &mut Hotel) {
+        say!("{}", tr!("wizard.start"));
+        for setting in Setting::ALL {
+            loop {
+                let question = tr!("wizard.ask", setting.label(), setting.current(hotel));
+                let Some(answer) = console::prompt(&format!("{} ", question)) else {
+                    say!("{}", tr!("input.ended"));
+                    return;
+                };
+                let answer = answer.trim();
+                if answer.is_empty() {
+                    break;
+                }
+                let kind = setting.kind();
+                let result = match kind.parse(answer) {
+                    Some(value) => validation::set(hotel, setting, &value),
+                    None => Err(tr!("wizard.expected", kind.expected())),
+                };
+                match result {
+                    // problems between settings are listed at the end, when all are known
+                    Ok(_) => break,
+                    Err(error) => say!("{}", error),
+                }
+            }
+        }
+        say!("{}", tr!("setup.config"));
+        say!("{}", hotel.describe_config());
+        for problem in validation::problems(hotel) {
+            say!("{}", tr!("setup.warning", problem.message));
         }
     }
 }
 
+const COMMANDS: CommandRegistry = CommandRegistry::new(&[
+    CommandSpec {
+        name: "new",
+        args: &[],
+        about: "about.setup.new",
+    },
+    CommandSpec {
+        name: "id",
+        args: &[Arg::required("id", ArgKind::Text)],
+        about: "about.setup.id",
+    },
+    CommandSpec {
+        name: "save",
+        args: &[],
+        about: "about.setup.save",
+    },
+    CommandSpec {
+        name: "rooms",
+        args: &[Arg::required("rooms", ArgKind::Positive)],
+        about: "about.setup.rooms",
+    },
+    CommandSpec {
+        name: "rps",
+        args: &[Arg::required("rooms_per_story", ArgKind::Positive)],
+        about: "about.setup.rps",
+    },
+    CommandSpec {
+        name: "elevator",
+        args: &[Arg::required("position", ArgKind::Index)],
+        about: "about.setup.elevator",
+    },
+    CommandSpec {
+        name: "building",
+        args: &[Arg::required("type", ArgKind::Choice(&BuildingType::NAMES))],
+        about: "about.setup.building",
+    },
+    CommandSpec {
+        name: "capital",
+        args: &[Arg::required("capital", ArgKind::Number)],
+        about: "about.setup.capital",
+    },
+    CommandSpec {
+        name: "fee",
+        args: &[Arg::required("fee", ArgKind::Number)],
+        about: "about.setup.fee",
+    },
+    CommandSpec {
+        name: "service",
+        args: &[Arg::required("daily_costs", ArgKind::Number)],
+        about: "about.setup.service",
+    },
+    CommandSpec {
+        name: "config",
+        args: &[],
+        about: "about.setup.config",
+    },
+    CommandSpec {
+        name: "wizard",
+        args: &[],
+        about: "about.setup.wizard",
+    },
+    CommandSpec {
+        name: "hotel set",
+        args: &[],
+        about: "about.setup.hotel_set",
+    },
+    CommandSpec {
+        name: "help",
+        args: &[Arg::optional("command", ArgKind::Rest)],
+        about: "about.setup.help",
+    },
+]);
+
 impl ManagerStateBehavior for SetUpHotelState {
     fn handle_command(
         &mut self,
         game_flow: &mut game_flow::GameFlow,
         input: &[&str],
     ) -> HandlingResult {
-        match input[0] {
+        let command = match COMMANDS.parse(input) {
+            Ok(command) => command,
+            Err(error) => {
+                say!("{}", error);
+                return HandlingResult::KeepState;
+            }
+        };
+        let hotel = &mut game_flow.hotel;
+        match command.name {
             "new" => {
-                println!("Hotel reset with a new random ID.");
+                say!("{}", tr!("setup.reset"));
                 return HandlingResult::ResetState;
             }
-            "id" if input.len() == 2 => {
-                self.set_hotel_id(game_flow, input[1].to_string());
+            "id" => {
+                self.set_hotel_id(game_flow, command.text("id").unwrap().to_string());
             }
-            "save" => match game_flow.hotel.save() {
+            "save" => match configs::save(hotel) {
                 Ok(_) => {
-                    println!("Hotel configuration saved.");
+                    say!("{}", tr!("setup.saved"));
                 }
                 _ => {
-                    println!("Error saving hotel configuration.");
+                    say!("{}", tr!("setup.save_failed"));
                 }
             },
-            "rooms" if input.len() == 2 => {
-                let prev_num_rooms = game_flow.hotel.num_rooms;
-                game_flow.hotel.num_rooms = input[1].parse().unwrap_or(prev_num_rooms);
-                println!("Number of rooms set to {}", input[1]);
-            }
-            "rps" if input.len() == 2 => {
-                game_flow.hotel.rooms_per_story = input[1].parse().unwrap_or(0);
-                println!("Rooms per story set to {}", input[1]);
-            }
-            "capital" if input.len() == 2 => {
-                game_flow.hotel.capital = input[1].parse().unwrap_or(0.0);
-                println!("Initial capital set to {}", input[1]);
-            }
-            "fee" if input.len() == 2 => {
-                game_flow.hotel.entrance_fee = input[1].parse().unwrap_or(0.0);
-                println!("Entrance fee set to {}", input[1]);
-            }
-            "service" if input.len() == 2 => {
-                game_flow.hotel.daily_costs = input[1].parse().unwrap_or(0.0);
-                println!("Daily service cost set to {}", input[1]);
+            "rooms" | "rps" | "elevator" | "building" | "capital" | "fee" | "service" => {
+                let setting = Setting::from_command(command.name).unwrap();
+                let value = command.only().unwrap().clone();
+                self.change(hotel, setting, &value);
             }
+            "wizard" => self.run_wizard(hotel),
             "config" => {
-                self.print_hotel_config(&game_flow.hotel);
+                self.print_hotel_config(hotel);
             }
-            "hotel" if input.len() >= 2 && input[1] == "set" => {
-                if game_flow.hotel.id.is_empty()
-                    || game_flow.hotel.num_rooms == 0
-                    || game_flow.hotel.capital == 0.0
-                    || game_flow.hotel.entrance_fee == 0.0
-                    || game_flow.hotel.daily_costs == 0.0
-                {
-                    println!("Please set all hotel properties before finalizing the setup.");
+            "hotel set" => {
+                let problems = validation::problems(hotel);
+                if hotel.id.is_empty() || !problems.is_empty() {
+                    say!("{}", tr!("setup.incomplete"));
+                    for problem in problems {
+                        say!("{}", tr!("setup.warning", problem.message));
+                    }
                 } else {
-                    game_flow.hotel.reinitialize();
-                    println!("Hotel setup complete. Moving to resident settlement stage.");
+                    hotel.reinitialize();
+                    say!("{}", tr!("setup.done"));
                     return HandlingResult::ChangeState;
                 }
             }
-            "help" => {
-                println!("Available commands:");
-                println!("new -- to reset all info about the hotel and generate a new random ID");
-                println!("id [id] -- to upload a hotel with existing ID, or set a new ID to the current hotel");
-                println!("save -- to save the configurations of the hotel with the ID");
-                println!("rooms [number of rooms] -- to set the number of rooms for the hotel");
-                println!(
-                    "rps [rooms per story] -- to set the number of rooms per story in the hotel"
-                );
-                println!("capital [initial capital] -- to set the initial capital for the hotel");
-                println!("fee [fee] -- to set the entrance fee for the residents to settle into the hotel");
-                println!(
-                    "service [daily costs per user] -- to set daily costs of the hotel per user"
-                );
-                println!("config -- to print the hotel configurations");
-                println!("hotel set -- to finish hotel settings and move on to the next stage");
-                println!("help -- to get current list of available commands")
-            }
-            _ => println!("Invalid command. Try 'help' for getting possible options."),
+            "help" => console::write(&COMMANDS.help(command.text("command"))),
+            _ => unreachable!("every registered command is handled"),
         }
         HandlingResult::KeepState
     }
@@ -118,7 +211,7 @@ impl ManagerStateBehavior for SetUpHotelState {
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::fs;
 
     use super::*;
     use crate::{
@@ -127,7 +220,7 @@ mod tests {
 
     fn run_commands(manager: &mut Manager, commands: &[&str]) {
         for command in commands {
-            let input: Vec<&str> = command.trim().split_whitespace().collect();
+            let input: Vec<&str> = command.split_whitespace().collect();
             if !input.is_empty() {
                 manager.handle_command(&input);
             }
@@ -292,13 +385,52 @@ mod tests {
         let mut manager =
             Manager::new_with_state(ManagerState::SetUpHotel(Box::new(SetUpHotelState)));
         let id = manager.game_flow.hotel.id.clone();
+        let directory = configs::use_temp_library("setup-save");
 
         let commands = vec!["save"];
 
         run_commands(&mut manager, &commands);
 
-        let path = format!("hotel_configs/{}.json", id);
-        assert!(Path::new(&path).exists());
+        assert!(directory.join(format!("{}.json", id)).exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_settings_that_clash_hold_the_setup() {
+        let mut manager =
+            Manager::new_with_state(ManagerState::SetUpHotel(Box::new(SetUpHotelState)));
+        run_commands(
+            &mut manager,
+            &["building pyramidal", "capital 0", "elevator 9", "hotel set"],
+        );
+
+        assert_eq!(manager.state().name(), "SetUpHotel");
+        assert_eq!(manager.game_flow.hotel.capital, 10000.0);
+        assert_eq!(manager.game_flow.hotel.building_type.name(), "pyramidal");
+        run_commands(&mut manager, &["rps 12", "hotel set"]);
+        assert_ne!(manager.state().name(), "SetUpHotel");
+    }
+
+    #[test]
+    fn test_wizard_asks_for_every_setting() {
+        let mut manager =
+            Manager::new_with_state(ManagerState::SetUpHotel(Box::new(SetUpHotelState)));
+        let answers = ["30", "", "one", "3", "lax", "", "-5", "50", "", ""];
+        let host = console::BufferChannel::new(answers.map(String::from).to_vec());
+        let output = host.output();
+        let previous = console::set_host(Some(Box::new(host)));
+
+        run_commands(&mut manager, &["wizard"]);
+        console::set_host(previous);
+
+        let hotel = &manager.game_flow.hotel;
+        assert_eq!((hotel.num_rooms, hotel.rooms_per_story), (30, 4));
+        assert_eq!(hotel.elevator_position, 3);
+        assert_eq!(hotel.building_type.name(), "lax");
+        assert_eq!((hotel.capital, hotel.entrance_fee), (10000.0, 50.0));
+        let output = output.borrow();
+        assert!(output.contains("Number of rooms [16]:"));
+        assert_eq!(output.matches("Please enter").count(), 2);
     }
 
     #[test]
@@ -0,0 +1,51 @@
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    static DICE: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Every random choice of the hotel comes from here, so that `--seed` replays a session.
+pub struct Dice;
+
+pub fn rng() -> Dice {
+    Dice
+}
+
+/// Makes the rolls from now on the same as in any run seeded alike.
+pub fn seed(seed: u64) {
+    DICE.with(|dice| *dice.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+impl RngCore for Dice {
+    fn next_u32(&mut self) -> u32 {
+        DICE.with(|dice| dice.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        DICE.with(|dice| dice.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        DICE.with(|dice| dice.borrow_mut().fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        DICE.with(|dice| dice.borrow_mut().try_fill_bytes(dest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_seeded_rolls_repeat() {
+        seed(7);
+        let first: Vec<u32> = (0..5).map(|_| rng().gen_range(0..100)).collect();
+        seed(7);
+        let second: Vec<u32> = (0..5).map(|_| rng().gen_range(0..100)).collect();
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,365 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::{
+    console,
+    game_history::{GameHistory, GAME_MASTER},
+    hotel::Hotel,
+    locale,
+    resident::{Resident, ResidentFactory, ResidentType, Status, SuperStatus},
+    roles::Role,
+    text_formatters::terminal_width,
+};
+
+const REPLAY_STYLE: &str = "#nsr";
+
+/// A resident as they were at some point of the game, hidden role included.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResident {
+    pub name: String,
+    pub age: usize,
+    pub balance: f64,
+    pub apartment: usize,
+    pub role: Role,
+    pub resident_type: ResidentType,
+    pub status: Status,
+    pub super_status: SuperStatus,
+}
+
+impl RecordedResident {
+    pub fn of(resident: &Resident) -> Self {
+        Self {
+            name: resident.name.clone(),
+            age: resident.age,
+            balance: resident.account_balance,
+            apartment: resident.apartment_number,
+            role: resident.strategy.confess_role(),
+            resident_type: resident.resident_type,
+            status: resident.status,
+            super_status: resident.super_status,
+        }
+    }
+
+    fn to_resident(&self) -> Resident {
+        let mut resident = ResidentFactory::create_resident(
+            self.name.clone(),
+            self.age,
+            self.balance,
+            self.apartment,
+            self.role,
+            self.resident_type,
+        );
+        resident.status = self.status;
+        resident.super_status = self.super_status;
+        resident
+    }
+}
+
+fn record_residents(hotel: &Hotel) -> Vec<RecordedResident> {
+    hotel
+        .get_all_residents()
+        .iter()
+        .map(|resident| RecordedResident::of(&resident.lock().unwrap()))
+        .collect()
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Setup {
+    pub hotel: Hotel, // the configuration only, apartments are rebuilt
+    pub residents: Vec<RecordedResident>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub enum Event {
+    Action {
+        day: usize,
+        actor: usize,
+        action: String,
+        target: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        victim: Option<usize>,
+    },
+    /// The residents as they woke up after the night of `day`.
+    Dawn {
+        day: usize,
+        residents: Vec<RecordedResident>,
+    },
+}
+
+impl Event {
+    pub fn day(&self) -> usize {
+        match self {
+            Event::Action { day, .. } | Event::Dawn { day, .. } => *day,
+        }
+    }
+}
+
+/// A whole game: how it was set up and everything that happened, in order.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Recording {
+    pub setup: Setup,
+    pub events: Vec<Event>,
+}
+
+impl Recording {
+    /// `dawns` are the hotels as they were on the morning after the night of a day.
+    pub fn new(setup: &Hotel, dawns: &[(usize, &Hotel)], history: &GameHistory) -> Self {
+        let last_day = history.actions.iter().map(|action| action.day).max();
+        let last_dawn = dawns.iter().map(|(day, _)| *day).max();
+        let mut events = vec![];
+        for day in 1..=last_day.max(last_dawn).unwrap_or(0) {
+            for action in history.actions.iter().filter(|action| action.day == day) {
+                events.push(Event::Action {
+                    day,
+                    actor: action.actor,
+                    action: action.action_type.clone(),
+                    target: action.target,
+                    victim: action.victim,
+                });
+            }
+            for (_, hotel) in dawns.iter().filter(|(dawn, _)| *dawn == day) {
+                events.push(Event::Dawn {
+                    day,
+                    residents: record_residents(hotel),
+                });
+            }
+        }
+        Self {
+            setup: Setup {
+                hotel: setup.clone(),
+                residents: record_residents(setup),
+            },
+            events,
+        }
+    }
+
+    pub fn path(id: &str) -> String {
+        format!("replays/{}.json", id)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        if let Some(directory) = Path::new(path).parent() {
+            fs::create_dir_all(directory)
+                .map_err(|e| format!("can't create {:?}: {}", directory, e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("can't write {}: {}", path, e))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path, e))?;
+        serde_json::from_str(&json).map_err(|e| format!("{} is not a recorded game: {}", path, e))
+    }
+
+    pub fn nights(&self) -> usize {
+        self.events.iter().map(Event::day).max().unwrap_or(0)
+    }
+}
+
+fn motive(role: Role) -> String {
+    tr!(&format!("motive.{:?}", role))
+}
+
+/// Steps through a recording night by night, showing everything that was hidden.
+pub struct Replay {
+    recording: Recording,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Self {
+        Self { recording }
+    }
+
+    pub fn nights(&self) -> usize {
+        self.recording.nights()
+    }
+
+    /// The hotel as it was at the start of the night of `night`.
+    fn hotel_before(&self, night: usize) -> Hotel {
+        let mut residents = &self.recording.setup.residents;
+        for event in &self.recording.events {
+            if let Event::Dawn {
+                day,
+                residents: dawn,
+            } = event
+            {
+                if *day < night {
+                    residents = dawn;
+                }
+            }
+        }
+        let mut hotel = self.recording.setup.hotel.clone();
+        hotel.apartments =
+            Hotel::initialize_apartments(hotel.num_rooms, hotel.rooms_per_story.max(1));
+        for resident in residents {
+            if let Some(apartment) = hotel.apartments.get_mut(resident.apartment) {
+                apartment.assign_resident(resident.to_resident());
+            }
+        }
+        hotel
+    }
+
+    fn resident(residents: &[RecordedResident], apartment: usize) -> Option<&RecordedResident> {
+        residents
+            .iter()
+            .find(|resident| resident.apartment == apartment)
+    }
+
+    fn describe(residents: &[RecordedResident], apartment: usize) -> String {
+        if apartment == GAME_MASTER {
+            return tr!("history.game_master");
+        }
+        match Self::resident(residents, apartment) {
+            Some(resident) => tr!("replay.resident", resident.name, resident.role, apartment),
+            None => tr!("replay.nobody", apartment),
+        }
+    }
+
+    /// The map at nightfall, every move of the night with its reason, and how the morning found everyone.
+    pub fn render_night(&self, night: usize) -> String {
+        let hotel = self.hotel_before(night);
+        let residents = record_residents(&hotel);
+        let mut output = format!("{}\n", tr!("replay.night", night, self.nights()));
+        output.push_str(&hotel.render_detailed(REPLAY_STYLE, terminal_width(), None));
+
+        let events = self
+            .recording
+            .events
+            .iter()
+            .filter(|event| event.day() == night);
+        for event in events {
+            match event {
+                Event::Action {
+                    actor,
+                    action,
+                    target,
+                    ..
+                } => {
+                    let reason = match Self::resident(&residents, *actor) {
+                        Some(actor) => tr!("replay.motive", actor.role, motive(actor.role)),
+                        None if *actor == GAME_MASTER => tr!("replay.game_master"),
+                        None => tr!("replay.no_reason"),
+                    };
+                    output.push_str(&format!(
+                        "{} -> {} -> {}\n    {}\n",
+                        Self::describe(&residents, *actor),
+                        locale::text_or(&format!("action.{}", action), action),
+                        Self::describe(&residents, *target),
+                        tr!("replay.because", reason)
+                    ));
+                }
+                Event::Dawn {
+                    residents: dawn, ..
+                } => {
+                    output.push_str(&format!("{}\n", tr!("replay.morning")));
+                    for after in dawn {
+                        let Some(before) = Self::resident(&residents, after.apartment) else {
+                            continue;
+                        };
+                        if before.status != after.status
+                            || before.super_status != after.super_status
+                        {
+                            output.push_str(&format!(
+                                "    {}\n",
+                                tr!(
+                                    "replay.status",
+                                    after.name,
+                                    tr!(&format!("status.{:?}", after.status)),
+                                    tr!(&format!("status.{:?}", after.super_status))
+                                )
+                            ));
+                        }
+                        if (before.balance - after.balance).abs() >= 0.005 {
+                            output.push_str(&format!(
+                                "    {}\n",
+                                tr!(
+                                    "replay.balance",
+                                    after.name,
+                                    format!("{:.2}", after.balance),
+                                    format!("{:.2}", before.balance)
+                                )
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    /// Shows the nights one by one until the viewer quits.
+    pub fn run(&self) {
+        let nights = self.nights();
+        if nights == 0 {
+            say!("{}", tr!("replay.empty"));
+            return;
+        }
+        let mut night = 1;
+        loop {
+            say!("{}", self.render_night(night));
+            let Some(input) = console::prompt(&tr!("replay.prompt")) else {
+                break;
+            };
+            match input.trim() {
+                "q" | "quit" => break,
+                "b" | "back" => night = night.saturating_sub(1).max(1),
+                "" if night == nights => break,
+                "" => night += 1,
+                other => match other.parse::<usize>() {
+                    Ok(day) if (1..=nights).contains(&day) => night = day,
+                    _ => say!("{}", tr!("replay.range", nights)),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded_game() -> Recording {
+        let hotel = Hotel::for_test(4, 2, &[("Kim", Role::Killer), ("Dan", Role::Doctor)]);
+
+        let morning = hotel.deep_clone();
+        morning.apartments[1]
+            .resident
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .status = Status::Dead;
+        let mut history = GameHistory::new();
+        history.add_action(0, "Kill".to_string(), 1, None);
+        history.add_action(1, "Heal".to_string(), 3, None);
+
+        Recording::new(&hotel, &[(1, &morning)], &history)
+    }
+
+    #[test]
+    fn test_recording_round_trips_through_json() {
+        let recording = recorded_game();
+        let json = serde_json::to_string(&recording).unwrap();
+        let loaded: Recording = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.events, recording.events);
+        assert_eq!(loaded.setup.residents, recording.setup.residents);
+        assert_eq!(loaded.nights(), 1);
+        assert!(matches!(
+            loaded.events.last(),
+            Some(Event::Dawn { day: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_night_shows_roles_reasons_and_outcome() {
+        let night = Replay::new(recorded_game()).render_night(1);
+
+        assert!(night.contains("Kim (Killer, apartment 0) -> Kill -> Dan (Doctor, apartment 1)"));
+        assert!(night.contains("because the Killer wants everyone else dead"));
+        assert!(night.contains("-> Heal -> nobody (apartment 3)"));
+        assert!(night.contains("Dan is Dead (None)"));
+    }
+}
@@ -0,0 +1,374 @@
+use std::fmt;
+
+use crate::{
+    hotel::Hotel,
+    locale,
+    replay::{Event, RecordedResident, Recording},
+    resident::{Resident, Status, SuperStatus},
+    roles::Role,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Winner {
+    Killers,
+    Residents,
+}
+
+impl fmt::Display for Winner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Winner::Killers => write!(f, "{}", tr!("report.killers")),
+            Winner::Residents => write!(f, "{}", tr!("report.residents")),
+        }
+    }
+}
+
+fn is_free(resident: &Resident) -> bool {
+    resident.status == Status::Alive && resident.super_status != SuperStatus::Arrested
+}
+
+/// The killers win once nobody else is free, the residents once every killer is dead or arrested.
+pub fn winner(hotel: &Hotel) -> Option<Winner> {
+    let residents = hotel.get_all_residents();
+    if residents.is_empty() {
+        return None;
+    }
+    let (mut killers, mut others) = (0, 0);
+    for resident in &residents {
+        let resident = resident.lock().unwrap();
+        if !is_free(&resident) {
+            continue;
+        }
+        if resident.strategy.confess_role() == Role::Killer {
+            killers += 1;
+        } else {
+            others += 1;
+        }
+    }
+    match (killers, others) {
+        (0, _) => Some(Winner::Residents),
+        (_, 0) => Some(Winner::Killers),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResidentReport {
+    pub name: String,
+    pub apartment: usize,
+    pub role: Role,
+    pub status: Status,
+    pub documents: Vec<String>, // "<role> of <name>" for every document they ended with
+    pub kills: usize,
+    pub money: f64, // gained since checking in, negative if lost
+    pub hits: usize,
+    pub misses: usize,
+    pub nights_survived: usize,
+}
+
+impl ResidentReport {
+    fn new(start: &RecordedResident, resident: &Resident) -> Self {
+        Self {
+            name: resident.name.clone(),
+            apartment: resident.apartment_number,
+            role: start.role,
+            status: resident.status,
+            documents: resident
+                .documents
+                .iter()
+                .map(|document| tr!("report.document", document.role, document.name))
+                .collect(),
+            kills: 0,
+            money: resident.account_balance - start.balance,
+            hits: 0,
+            misses: 0,
+            nights_survived: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Award {
+    pub title: String,
+    pub name: String,
+    pub reason: String,
+}
+
+pub struct Report {
+    pub winner: Option<Winner>,
+    pub nights: usize,
+    pub residents: Vec<ResidentReport>,
+    pub awards: Vec<Award>,
+}
+
+impl Report {
+    /// Compares how the game started (from its recording) with how the hotel looks now.
+    pub fn new(recording: &Recording, hotel: &Hotel) -> Self {
+        let start = &recording.setup.residents;
+        let mut residents: Vec<ResidentReport> = start
+            .iter()
+            .filter_map(|start| {
+                let resident = hotel.apartments.get(start.apartment)?.resident.as_ref()?;
+                Some(ResidentReport::new(start, &resident.lock().unwrap()))
+            })
+            .collect();
+
+        for event in &recording.events {
+            match event {
+                Event::Action {
+                    actor,
+                    action,
+                    target,
+                    victim,
+                    ..
+                } => {
+                    let target_role = start
+                        .iter()
+                        .find(|r| r.apartment == *target)
+                        .map(|r| r.role);
+                    let Some(report) = residents.iter_mut().find(|r| r.apartment == *actor) else {
+                        continue;
+                    };
+                    match action.as_str() {
+                        _ if victim.is_some() => report.kills += 1,
+                        "Investigate" if target_role == Some(Role::Killer) => report.hits += 1,
+                        "Investigate" => report.misses += 1,
+                        _ => {}
+                    }
+                }
+                Event::Dawn {
+                    residents: dawn, ..
+                } => {
+                    for report in residents.iter_mut() {
+                        let alive = dawn
+                            .iter()
+                            .any(|r| r.apartment == report.apartment && r.status == Status::Alive);
+                        if alive {
+                            report.nights_survived += 1;
+                        }
+                    }
+                }
+            }
+        }
+        let awards = Self::awards(&residents);
+        Self {
+            winner: winner(hotel),
+            nights: recording.nights(),
+            residents,
+            awards,
+        }
+    }
+
+    fn awards(residents: &[ResidentReport]) -> Vec<Award> {
+        type Candidate<'a> = (
+            &'static str, // the key of the title in the catalogue
+            Option<&'a ResidentReport>,
+            fn(&ResidentReport) -> String,
+        );
+        let by_money = |a: &&ResidentReport, b: &&ResidentReport| a.money.total_cmp(&b.money);
+        let candidates: [Candidate; 4] = [
+            (
+                "report.best_detective",
+                residents
+                    .iter()
+                    .filter(|r| r.hits > 0)
+                    .max_by_key(|r| r.hits),
+                |r| locale::plural("report.found_killer", r.hits, &[]),
+            ),
+            (
+                "report.greediest_swindler",
+                residents
+                    .iter()
+                    .filter(|r| r.role == Role::Swindler && r.money > 0.0)
+                    .max_by(by_money),
+                |r| tr!("report.made", format!("{:.2}", r.money)),
+            ),
+            (
+                "report.deadliest_hand",
+                residents
+                    .iter()
+                    .filter(|r| r.kills > 0)
+                    .max_by_key(|r| r.kills),
+                |r| locale::plural("report.kills", r.kills, &[]),
+            ),
+            (
+                "report.biggest_loser",
+                residents.iter().filter(|r| r.money < 0.0).min_by(by_money),
+                |r| tr!("report.lost", format!("{:.2}", -r.money)),
+            ),
+        ];
+        candidates
+            .into_iter()
+            .filter_map(|(title, best, reason)| {
+                let best = best?;
+                Some(Award {
+                    title: tr!(title),
+                    name: best.name.clone(),
+                    reason: reason(best),
+                })
+            })
+            .collect()
+    }
+
+    fn headline(&self) -> String {
+        match self.winner {
+            Some(winner) => locale::plural("report.won", self.nights, &[&winner]),
+            None => locale::plural("report.nobody_won", self.nights, &[]),
+        }
+    }
+
+    fn documents(report: &ResidentReport) -> String {
+        if report.documents.is_empty() {
+            tr!("report.no_documents")
+        } else {
+            report.documents.join(", ")
+        }
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut output = format!("{}\n{}\n", tr!("report.title_text"), self.headline());
+        for r in &self.residents {
+            output.push_str(&format!(
+                "{}\n",
+                tr!(
+                    "report.resident",
+                    r.name,
+                    r.apartment,
+                    r.role,
+                    tr!(&format!("status.{:?}", r.status)),
+                    r.nights_survived,
+                    Self::documents(r),
+                    r.kills,
+                    format!("{:+.2}", r.money),
+                    r.hits,
+                    r.misses
+                )
+            ));
+        }
+        if !self.awards.is_empty() {
+            output.push_str(&format!("{}\n", tr!("report.awards")));
+            for award in &self.awards {
+                output.push_str(&format!(
+                    "    {}: {} ({})\n",
+                    award.title, award.name, award.reason
+                ));
+            }
+        }
+        output
+    }
+
+    pub fn render_markdown(&self) -> String {
+        let mut output = format!("# {}\n\n{}\n\n", tr!("report.title"), self.headline());
+        output.push_str(&format!("{}\n", tr!("report.table_header")));
+        output.push_str("|---|---|---|---|---|---|---|---|---|\n");
+        for r in &self.residents {
+            output.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {:+.2} | {}/{} |\n",
+                r.name.replace('|', "\\|"),
+                r.apartment,
+                r.role,
+                Self::documents(r).replace('|', "\\|"),
+                tr!(&format!("status.{:?}", r.status)),
+                r.nights_survived,
+                r.kills,
+                r.money,
+                r.hits,
+                r.misses
+            ));
+        }
+        if !self.awards.is_empty() {
+            output.push_str(&format!("\n## {}\n\n", tr!("report.awards_title")));
+            for award in &self.awards {
+                output.push_str(&format!(
+                    "- **{}**: {} ({})\n",
+                    award.title, award.name, award.reason
+                ));
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_history::GameHistory;
+
+    fn hotel_of(residents: &[(&str, Role)]) -> Hotel {
+        Hotel::for_test(4, 2, residents)
+    }
+
+    fn set(hotel: &Hotel, apartment: usize, change: impl FnOnce(&mut Resident)) {
+        change(
+            &mut hotel.apartments[apartment]
+                .resident
+                .as_ref()
+                .unwrap()
+                .lock()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_report_counts_kills_money_and_investigations() {
+        let start = hotel_of(&[
+            ("Kim", Role::Killer),
+            ("Dan", Role::Doctor),
+            ("Pat", Role::Police),
+            ("Sue", Role::Swindler),
+        ]);
+        let mut history = GameHistory::new();
+        history.add_attack(0, "Kill".to_string(), 1, Some(1));
+        history.add_action(2, "Investigate".to_string(), 0, None);
+        history.next_day();
+        // Dan died the night before, so this attack finds nobody
+        history.add_attack(0, "Kill".to_string(), 1, None);
+        history.add_action(2, "Investigate".to_string(), 3, Some(2));
+        history.add_action(3, "Swindle".to_string(), 2, Some(2));
+        let first_dawn = start.deep_clone();
+        set(&first_dawn, 1, |r| r.status = Status::Dead);
+        let end = first_dawn.deep_clone();
+        set(&end, 0, |r| r.super_status = SuperStatus::Arrested);
+        set(&end, 3, |r| r.account_balance = 150.0);
+        set(&end, 2, |r| r.account_balance = 50.0);
+        let recording = Recording::new(&start, &[(1, &first_dawn), (2, &end)], &history);
+
+        let report = Report::new(&recording, &end);
+
+        assert_eq!(report.winner, Some(Winner::Residents));
+        let kim = &report.residents[0];
+        assert_eq!((kim.kills, kim.nights_survived), (1, 2));
+        assert_eq!(report.residents[1].nights_survived, 0);
+        let pat = &report.residents[2];
+        assert_eq!((pat.hits, pat.misses, pat.money), (1, 1, -50.0));
+        assert_eq!(report.residents[3].documents, vec!["Swindler of Sue"]);
+        let awards: Vec<(&str, &str)> = report
+            .awards
+            .iter()
+            .map(|award| (award.title.as_str(), award.name.as_str()))
+            .collect();
+        assert_eq!(
+            awards,
+            vec![
+                ("Best Detective", "Pat"),
+                ("Greediest Swindler", "Sue"),
+                ("Deadliest Hand", "Kim"),
+                ("Biggest Loser", "Pat"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_report_is_a_table() {
+        let hotel = hotel_of(&[("Kim", Role::Killer), ("Dan | Jr", Role::Doctor)]);
+        set(&hotel, 1, |r| r.status = Status::Dead);
+        let recording = Recording::new(&hotel, &[], &GameHistory::new());
+
+        let markdown = Report::new(&recording, &hotel).render_markdown();
+
+        assert!(markdown.starts_with("# Game report\n\nThe killers won after 0 nights."));
+        assert!(markdown.contains("| Dan \\| Jr | 1 | Doctor |"));
+        assert!(!markdown.contains("## Awards"));
+        assert_eq!(winner(&hotel_of(&[])), None);
+    }
+}
@@ -1,10 +1,15 @@
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::sync::Arc;
+use strum_macros::{EnumIter, EnumVariantNames};
 
 use crate::{
-    document::Document,
+    console,
+    document::{self, Document, Face},
     game_history,
     hotel::Hotel,
+    identity, random,
     roles::Role,
     strategies::{
         _strategy::ResidentStrategy, avenger_strategy::AvengerStrategy,
@@ -15,13 +20,16 @@ use crate::{
     },
 };
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// How likely a bot is to lock their door for the night.
+const LOCK_CHANCE: f64 = 0.3;
+
+#[derive(EnumIter, EnumVariantNames, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Status {
     Alive,
     Dead,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(EnumIter, EnumVariantNames, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SuperStatus {
     // todo: implement special move logic
     Asleep,         // alive, but sleeps full night
@@ -38,12 +46,30 @@ pub enum SuperStatus {
     None,           // person is okay
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ResidentType {
     Human,
     Bot,
 }
 
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr!(&format!("status.{:?}", self)))
+    }
+}
+
+impl fmt::Display for SuperStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr!(&format!("status.{:?}", self)))
+    }
+}
+
+impl fmt::Display for ResidentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr!(&format!("type.{:?}", self)))
+    }
+}
+
 #[derive(Clone)]
 pub struct Resident {
     pub name: String,
@@ -54,7 +80,11 @@ pub struct Resident {
     pub super_status: SuperStatus,
     pub resident_type: ResidentType,
     pub documents: Vec<Document>,
+    pub face: Face,       // how they really look, whatever their documents show
+    pub keys: Vec<usize>, // apartments whose doors they can open
     pub strategy: Arc<dyn ResidentStrategy>,
+    pub pin: Option<String>, // asked for before their turn on a shared screen
+    pub traits: Vec<String>, // the character of a bot, written into their documents
 }
 
 impl Resident {
@@ -66,8 +96,13 @@ impl Resident {
         strategy: Arc<dyn ResidentStrategy>,
         resident_type: ResidentType,
     ) -> Resident {
-        let mut documents = Vec::new();
-        documents.push(Document::new(strategy.confess_role(), name.clone(), age));
+        let face = Face::random();
+        let documents = vec![Document::new(
+            strategy.confess_role(),
+            name.clone(),
+            age,
+            face,
+        )];
         Resident {
             name,
             age,
@@ -77,7 +112,11 @@ impl Resident {
             super_status: SuperStatus::None,
             resident_type,
             documents,
+            face,
+            keys: vec![apartment_number], // handed over at check-in
             strategy,
+            pin: None,
+            traits: vec![],
         }
     }
 
@@ -86,6 +125,16 @@ impl Resident {
         self.status = other.status;
         self.super_status = other.super_status;
         self.documents = other.documents;
+        self.keys = other.keys;
+    }
+
+    /// Locks their door until the morning, if they still hold the key to it.
+    pub fn lock_door(&self, hotel: &mut Hotel) -> bool {
+        if !self.keys.contains(&self.apartment_number) {
+            return false;
+        }
+        hotel.apartments[self.apartment_number].is_opened = false;
+        true
     }
 
     pub fn is_ready(&self) -> bool {
@@ -97,7 +146,7 @@ impl Resident {
 
     pub fn update_state(&mut self) {
         match self.super_status {
-            SuperStatus::Visionary => {}
+            SuperStatus::Visionary | SuperStatus::Arrested => {}
             SuperStatus::Unconscious => {
                 self.super_status = SuperStatus::Asleep;
             }
@@ -111,117 +160,76 @@ impl Resident {
     }
 
     pub fn perform_action(&mut self, hotel: &mut Hotel, history: &mut game_history::GameHistory) {
+        let previous_focus = console::focus(Some(self.apartment_number));
+        self.act(hotel, history);
+        console::focus(previous_focus);
+    }
+
+    fn act(&mut self, hotel: &mut Hotel, history: &mut game_history::GameHistory) {
         if self.status != Status::Alive {
-            println!("Dead are not allowed to move...");
+            say!("{}", tr!("resident.dead"));
             return;
         }
         if self.super_status != SuperStatus::None {
-            println!("Super status is not None...");
+            say!("{}", tr!("resident.super_status"));
             // todo!();
             if self.super_status == SuperStatus::Disinterested
                 || self.super_status == SuperStatus::Asleep
+                || self.super_status == SuperStatus::Arrested
             {
-                println!("Let's not move, shall we?..");
+                say!("{}", tr!("resident.skips"));
                 return;
             }
         }
         hotel.apartments[self.apartment_number].read_mails();
+        if self.resident_type == ResidentType::Bot
+            && random::rng().gen_bool(LOCK_CHANCE)
+            && self.lock_door(hotel)
+        {
+            say!("{}", tr!("door.locked", self.apartment_number));
+        }
         let mut self_clone = self.clone();
         self.strategy
             .perform_action(&mut self_clone, hotel, history);
+        if self.strategy.leaves_home() {
+            if let Some(target) = history.visit_of(self.apartment_number) {
+                hotel.arrive(&mut self_clone, target);
+            }
+        }
         self.copy_fields(self_clone);
     }
 }
 
-use std::fmt;
-
 impl fmt::Display for Resident {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keys: Vec<String> = self.keys.iter().map(usize::to_string).collect();
+        let documents = if self.documents.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}", document::list(&self.documents))
+        };
         write!(
             f,
             "{}",
-            format!(
-                "{}, {} y.o., {:?}. Account balance: {}, status: {:?}, documents: {}",
+            tr!(
+                "resident.describe",
                 self.name,
                 self.age,
                 self.resident_type,
                 self.account_balance,
                 self.status,
-                self.documents
-                    .iter()
-                    .enumerate()
-                    .map(|(dx, doc)| format!("\n{}.\n{}", dx, doc))
-                    .collect::<String>()
+                keys.join(", "),
+                documents
             )
         )
     }
 }
 
-const NAMES: [&'static str; 50] = [
-    "Alice Johnson",
-    "Bob Smith",
-    "Charlie Brown",
-    "Diana White",
-    "Eve Black",
-    "Frank Green",
-    "Grace Walker",
-    "Hank Hall",
-    "Ivy Adams",
-    "Jack King",
-    "Kathy Scott",
-    "Larry Harris",
-    "Mona Lewis",
-    "Nate Lee",
-    "Olivia Young",
-    "Peter Wright",
-    "Quinn Wood",
-    "Rachel Fisher",
-    "Sam Brooks",
-    "Tina Bell",
-    "Uma Evans",
-    "Victor Moore",
-    "Wendy Clark",
-    "Xander Cole",
-    "Yvonne Price",
-    "Zane Murphy",
-    "Amy Rogers",
-    "Brian Hughes",
-    "Cindy Edwards",
-    "David Turner",
-    "Ella Baker",
-    "Fred Nelson",
-    "Gina Cox",
-    "Harry Carter",
-    "Isla Mitchell",
-    "Jason Parker",
-    "Karen Roberts",
-    "Liam Phillips",
-    "Mia Campbell",
-    "Nick Perez",
-    "Oscar Russell",
-    "Paula Stewart",
-    "Quincy Diaz",
-    "Rebecca Myers",
-    "Steve Ortiz",
-    "Tracy Nguyen",
-    "Ursula Gray",
-    "Vince Simmons",
-    "Wanda Long",
-    "Xenia Foster",
-];
-
 pub struct ResidentFactory;
 
 impl ResidentFactory {
-    pub fn create_resident(
-        name: String,
-        age: usize,
-        account_balance: f64,
-        apartment_number: usize,
-        role: Role,
-        resident_type: ResidentType,
-    ) -> Resident {
-        let strategy: Arc<dyn ResidentStrategy> = match role {
+    pub fn strategy(role: Role) -> Arc<dyn ResidentStrategy> {
+        match role {
             Role::Killer => Arc::new(KillerStrategy),
             Role::Police => Arc::new(PoliceStrategy),
             Role::Doctor => Arc::new(DoctorStrategy),
@@ -231,37 +239,44 @@ impl ResidentFactory {
             Role::Avenger => Arc::new(AvengerStrategy),
             Role::Judge => Arc::new(JudgeStrategy),
             Role::Professor => Arc::new(ProfessorStrategy),
-        };
+        }
+    }
 
+    pub fn create_resident(
+        name: String,
+        age: usize,
+        account_balance: f64,
+        apartment_number: usize,
+        role: Role,
+        resident_type: ResidentType,
+    ) -> Resident {
         Resident::new(
             name,
             age,
             account_balance,
             apartment_number,
-            strategy,
+            Self::strategy(role),
             resident_type,
         )
     }
 
-    pub fn generate_random(apartment: usize, role: Role) -> Resident {
-        let mut rng = rand::thread_rng();
+    /// A bot with a name none of the `taken` have, and a character of their own.
+    pub fn generate_random(apartment: usize, role: Role, taken: &[String]) -> Resident {
+        let mut rng = random::rng();
 
-        let name = NAMES[rng.gen_range(0..NAMES.len())].to_string();
+        let name = identity::unique_name(taken);
         let age = rng.gen_range(18..81);
         let account_balance = rng.gen_range(1000.0..10000.0);
-        Self::create_resident(
+        let mut bot = Self::create_resident(
             name,
             age,
             account_balance,
             apartment,
             role,
             ResidentType::Bot,
-        )
-    }
-}
-
-impl Default for Role {
-    fn default() -> Self {
-        Role::Killer // Default role, change as needed
+        );
+        bot.traits = identity::traits();
+        bot.documents[0].traits = bot.traits.clone();
+        bot
     }
 }
@@ -1,9 +1,13 @@
 use core::fmt;
-use strum_macros::EnumIter;
+use serde::{Deserialize, Serialize};
+use strum_macros::{EnumIter, EnumVariantNames};
 
-#[derive(EnumIter, Debug, Clone, Copy, PartialEq)]
+#[derive(
+    EnumIter, EnumVariantNames, Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize,
+)]
 pub enum Role {
-    Killer,
+    #[default]
+    Killer, // Default role, change as needed
     Police,
     Doctor,
     Janitor,
@@ -16,17 +20,6 @@ pub enum Role {
 
 impl fmt::Display for Role {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let role_str = match self {
-            Role::Killer => "Killer",
-            Role::Police => "Police",
-            Role::Doctor => "Doctor",
-            Role::Janitor => "Janitor",
-            Role::OldLady => "OldLady",
-            Role::Swindler => "Swindler",
-            Role::Avenger => "Avenger",
-            Role::Judge => "Judge",
-            Role::Professor => "Professor",
-        };
-        write!(f, "{}", role_str)
+        write!(f, "{}", tr!(&format!("role.{:?}", self)))
     }
 }
@@ -0,0 +1,321 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use strum::IntoEnumIterator;
+
+use crate::document::OLDEST_AGE;
+use crate::hotel::Hotel;
+use crate::resident::{ResidentFactory, ResidentType};
+use crate::roles::Role;
+
+/// One resident of a roster, checked and ready to move in.
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub age: usize,
+    pub balance: f64,
+    pub apartment: Option<usize>, // the next free room when not given
+    pub role: Option<Role>,       // fixed for scenarios, drawn like anyone's when not given
+    pub pin: Option<String>,      // asked for before each of their turns when given
+}
+
+/// The fields of a row as written, keyed by column, with the number to report it by.
+type Row = (usize, HashMap<String, String>);
+
+/// Splits a CSV line at the commas outside quotes; `""` inside quotes is a quote.
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+        .iter()
+        .map(|field| field.trim().to_string())
+        .collect()
+}
+
+/// Rows of a CSV file with a header line, numbered by the line they are on.
+fn csv_rows(text: &str) -> Vec<Row> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return vec![];
+    };
+    let columns: Vec<String> = split_csv(header)
+        .iter()
+        .map(|column| column.to_lowercase())
+        .collect();
+    lines
+        .map(|(index, line)| {
+            let fields = columns.iter().cloned().zip(split_csv(line));
+            (
+                index + 1,
+                fields.filter(|(_, field)| !field.is_empty()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Rows of a JSON list of objects, numbered from 1.
+fn json_rows(text: &str, path: &str) -> Result<Vec<Row>, String> {
+    let list: Vec<Value> =
+        serde_json::from_str(text).map_err(|e| tr!("roster.bad_json", path, e))?;
+    list.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let object = row
+                .as_object()
+                .ok_or_else(|| tr!("roster.bad_json", path, tr!("roster.not_object", index + 1)))?;
+            let fields = object.iter().filter_map(|(key, value)| {
+                let value = match value {
+                    Value::Null => return None,
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                Some((key.to_lowercase(), value))
+            });
+            Ok((index + 1, fields.collect()))
+        })
+        .collect()
+}
+
+fn parse_role(name: &str) -> Option<Role> {
+    let name: String = name.split_whitespace().collect();
+    Role::iter().find(|role| format!("{:?}", role).eq_ignore_ascii_case(&name))
+}
+
+/// The entry of a row, or everything wrong with it on its own.
+fn entry(fields: &HashMap<String, String>) -> Result<Entry, Vec<String>> {
+    let mut errors = vec![];
+    let mut field = |key: &str| {
+        let value = fields.get(key).cloned();
+        if value.is_none() {
+            errors.push(tr!("roster.missing", key));
+        }
+        value
+    };
+    let name = field("name");
+    let age = field("age");
+    let balance = field("balance");
+
+    let age = age.and_then(|age| match age.parse::<usize>() {
+        Ok(age) if (1..=OLDEST_AGE).contains(&age) => Some(age),
+        _ => {
+            errors.push(tr!("roster.bad_age", age, OLDEST_AGE));
+            None
+        }
+    });
+    let balance = balance.and_then(|balance| match balance.parse::<f64>() {
+        Ok(amount) if amount.is_finite() && amount >= 0.0 => Some(amount),
+        _ => {
+            errors.push(tr!("roster.bad_balance", balance));
+            None
+        }
+    });
+    let apartment = fields.get("apartment").and_then(|apartment| {
+        let number = apartment.parse::<usize>().ok();
+        if number.is_none() {
+            errors.push(tr!("roster.bad_apartment", apartment));
+        }
+        number
+    });
+    let role = fields.get("role").and_then(|name| {
+        let role = parse_role(name);
+        if role.is_none() {
+            errors.push(tr!("roster.bad_role", name));
+        }
+        role
+    });
+    match (name, age, balance) {
+        (Some(name), Some(age), Some(balance)) if errors.is_empty() => Ok(Entry {
+            name,
+            age,
+            balance,
+            apartment,
+            role,
+            pin: fields.get("pin").cloned(),
+        }),
+        _ => Err(errors),
+    }
+}
+
+/// The rows of the roster at `path`: a JSON list when it ends in `.json`, CSV otherwise.
+fn rows(path: &str) -> Result<Vec<Row>, String> {
+    let text = fs::read_to_string(path).map_err(|e| tr!("roster.unreadable", path, e))?;
+    let rows = if path.to_lowercase().ends_with(".json") {
+        json_rows(&text, path)?
+    } else {
+        csv_rows(&text)
+    };
+    if rows.is_empty() {
+        return Err(tr!("roster.empty", path));
+    }
+    Ok(rows)
+}
+
+/// The entries of a roster, checked against each other and against who already lives in `hotel`.
+/// Every problem is reported, each with its row.
+pub fn read(hotel: &Hotel, path: &str) -> Result<Vec<Entry>, Vec<String>> {
+    let rows = rows(path).map_err(|error| vec![error])?;
+    let mut errors = vec![];
+    let mut entries = vec![];
+    let mut names: HashMap<String, usize> = HashMap::new();
+    let mut apartments: HashMap<usize, usize> = HashMap::new();
+    let mut roles_left: Vec<Role> = hotel.available_roles.clone();
+    let living: Vec<String> = hotel
+        .get_all_residents()
+        .iter()
+        .map(|resident| resident.lock().unwrap().name.to_lowercase())
+        .collect();
+
+    for (number, fields) in &rows {
+        let mut problems = vec![];
+        match entry(fields) {
+            Err(found) => problems.extend(found),
+            Ok(entry) => {
+                let name = entry.name.to_lowercase();
+                if living.contains(&name) {
+                    problems.push(tr!("roster.name_taken", entry.name));
+                } else if let Some(first) = names.get(&name) {
+                    problems.push(tr!("roster.same_name", entry.name, first));
+                } else {
+                    names.insert(name, *number);
+                }
+                if let Some(apartment) = entry.apartment {
+                    if apartment >= hotel.apartments.len() {
+                        problems.push(tr!("roster.no_apartment", apartment));
+                    } else if !hotel.is_room_available(apartment) {
+                        problems.push(tr!("roster.room_taken", apartment));
+                    } else if let Some(first) = apartments.get(&apartment) {
+                        problems.push(tr!("roster.same_room", apartment, first));
+                    } else {
+                        apartments.insert(apartment, *number);
+                    }
+                }
+                if let Some(role) = entry.role {
+                    match roles_left.iter().position(|left| *left == role) {
+                        Some(index) => {
+                            roles_left.remove(index);
+                        }
+                        None => problems.push(tr!("roster.no_role", role)),
+                    }
+                }
+                if problems.is_empty() {
+                    entries.push(entry);
+                }
+            }
+        }
+        errors.extend(
+            problems
+                .into_iter()
+                .map(|problem| tr!("roster.row", number, problem)),
+        );
+    }
+    let free = hotel.available_rooms_count();
+    if rows.len() > free {
+        errors.push(tr!("roster.too_many", rows.len(), free));
+    }
+    if errors.is_empty() {
+        Ok(entries)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Settles everyone in the roster at `path`, or nobody if any row is wrong.
+/// Returns how many moved in.
+pub fn import(
+    hotel: &mut Hotel,
+    path: &str,
+    resident_type: ResidentType,
+) -> Result<usize, Vec<String>> {
+    let entries = read(hotel, path)?;
+    // fixed roles and rooms are taken out first, so that the rest can't draw them
+    for role in entries.iter().filter_map(|entry| entry.role) {
+        let index = hotel.available_roles.iter().position(|left| *left == role);
+        hotel.available_roles.remove(index.unwrap());
+    }
+    let wanted: Vec<usize> = entries.iter().filter_map(|entry| entry.apartment).collect();
+    let mut free = hotel
+        .available_rooms()
+        .into_iter()
+        .filter(|room| !wanted.contains(room));
+    let count = entries.len();
+    for entry in entries {
+        let apartment = entry.apartment.or_else(|| free.next()).unwrap();
+        let role = entry
+            .role
+            .or_else(|| hotel.random_available_role())
+            .unwrap();
+        let mut resident = ResidentFactory::create_resident(
+            entry.name,
+            entry.age,
+            entry.balance,
+            apartment,
+            role,
+            resident_type,
+        );
+        resident.pin = entry.pin;
+        hotel.add_resident(resident, apartment);
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hotel() -> Hotel {
+        Hotel::for_test(6, 3, &[])
+    }
+
+    #[test]
+    fn test_csv_and_json_rosters_read_alike() {
+        let hotel = hotel();
+        let csv = read(&hotel, "inputs/rosters/league.csv").unwrap();
+        let json = read(&hotel, "inputs/rosters/league.json").unwrap();
+
+        assert_eq!(csv, json);
+        assert_eq!(csv[0].name, "Alice Johnson, Jr.");
+        assert_eq!(csv[1].role, Some(Role::OldLady));
+        assert_eq!(csv[2].apartment, None);
+        assert_eq!(csv[0].pin.as_deref(), Some("0451"));
+        assert_eq!(csv[1].pin, None);
+        assert_eq!(split_csv(r#"a, "b ""c"", d" ,"#), ["a", r#"b "c", d"#, ""]);
+    }
+
+    #[test]
+    fn test_a_roster_with_errors_settles_nobody() {
+        let mut hotel = hotel();
+        let errors =
+            import(&mut hotel, "inputs/rosters/broken.csv", ResidentType::Human).unwrap_err();
+
+        assert_eq!(hotel.available_rooms_count(), 6);
+        assert_eq!(errors.len(), 6);
+        assert!(errors.iter().any(|error| error.contains("999999")));
+        assert!(errors[0].starts_with("Row 2:"));
+        assert!(errors.last().unwrap().contains("don't fit"));
+
+        let count = import(&mut hotel, "inputs/rosters/league.csv", ResidentType::Human).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(hotel.available_rooms_count(), 3);
+        assert!(import(
+            &mut hotel,
+            "inputs/rosters/league.json",
+            ResidentType::Human
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::{resident::Resident, roles::Role};
+
+/// Who the shared screen is shown to.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Scope {
+    GameMaster, // sees every role, balance and move
+    #[default]
+    Player, // sees what the resident whose turn it is knows
+}
+
+impl Scope {
+    pub const NAMES: [&'static str; 2] = ["gm", "player"];
+
+    pub fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "gm" => Some(Scope::GameMaster),
+            "player" => Some(Scope::Player),
+            _ => None,
+        }
+    }
+}
+
+/// What a resident legitimately knows about the others: their own role and money,
+/// the roles written in the documents they hold, and whatever the bulletin board says.
+pub struct Viewer {
+    pub apartment: Option<usize>,
+    known_roles: HashMap<String, Role>,
+}
+
+impl Viewer {
+    pub fn of(resident: &Resident) -> Self {
+        let known_roles = resident
+            .documents
+            .iter()
+            .map(|document| (document.name.clone(), document.role))
+            .collect();
+        Self {
+            apartment: Some(resident.apartment_number),
+            known_roles,
+        }
+    }
+
+    /// Someone who has only seen the bulletin board.
+    pub fn nobody() -> Self {
+        Self {
+            apartment: None,
+            known_roles: HashMap::new(),
+        }
+    }
+
+    pub fn lives_in(&self, apartment: usize) -> bool {
+        self.apartment == Some(apartment)
+    }
+
+    pub fn known_role(&self, name: &str) -> Option<Role> {
+        self.known_roles.get(name).copied()
+    }
+}
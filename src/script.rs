@@ -0,0 +1,218 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::rc::Rc;
+
+use crate::{
+    apartment::Apartment,
+    command,
+    console::{self, Channel},
+    manager::Manager,
+};
+
+type Lines = Rc<RefCell<VecDeque<(usize, String)>>>;
+
+#[derive(Debug, PartialEq)]
+pub struct ScriptError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            0 => write!(f, "{}", self.message),
+            line => write!(f, "{}", tr!("script.line", line, self.message)),
+        }
+    }
+}
+
+/// Answers the questions asked during a move with the next lines of the script.
+struct ScriptChannel {
+    lines: Lines,
+    ran_out: Rc<Cell<bool>>, // set once a question found no line left to answer it
+}
+
+impl Channel for ScriptChannel {
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        print!("{}", text);
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let line = self.lines.borrow_mut().pop_front();
+        if line.is_none() {
+            self.ran_out.set(true);
+        }
+        Ok(line.map(|(_, line)| line))
+    }
+}
+
+pub fn run_file(manager: &mut Manager, path: &str) -> Result<(), ScriptError> {
+    let source = fs::read_to_string(path).map_err(|error| ScriptError {
+        line: 0,
+        message: tr!("script.cant_read", path, error),
+    })?;
+    run(manager, &source)
+}
+
+/// Runs every command of `source`, stopping at the first failed `expect`.
+/// Lines starting with `#` are comments.
+pub fn run(manager: &mut Manager, source: &str) -> Result<(), ScriptError> {
+    let lines: Lines = Rc::new(RefCell::new(
+        source
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim().to_string()))
+            .filter(|(_, line)| !line.starts_with('#'))
+            .collect(),
+    ));
+    let ran_out = Rc::new(Cell::new(false));
+    let previous_host = console::set_host(Some(Box::new(ScriptChannel {
+        lines: Rc::clone(&lines),
+        ran_out: Rc::clone(&ran_out),
+    })));
+    let result = run_lines(manager, &lines, &ran_out);
+    console::set_host(previous_host);
+    result
+}
+
+fn run_lines(
+    manager: &mut Manager,
+    lines: &Lines,
+    ran_out: &Cell<bool>,
+) -> Result<(), ScriptError> {
+    loop {
+        let Some((number, line)) = lines.borrow_mut().pop_front() else {
+            return Ok(());
+        };
+        let error = |message: String| ScriptError {
+            line: number,
+            message,
+        };
+        let tokens = command::tokenize(&line).map_err(|e| error(e.to_string()))?;
+        let input: Vec<&str> = tokens.iter().map(String::as_str).collect();
+        match input.as_slice() {
+            [] => {}
+            ["expect", expectation @ ..] => check(manager, expectation).map_err(error)?,
+            _ => {
+                println!("    => {}", line);
+                manager.handle_command(&input);
+                if ran_out.get() {
+                    return Err(error(tr!("script.ran_out")));
+                }
+            }
+        }
+    }
+}
+
+fn compare(what: &str, expected: &str, actual: String) -> Result<(), String> {
+    let matches = match (expected.parse::<f64>(), actual.parse::<f64>()) {
+        (Ok(expected), Ok(actual)) => (expected - actual).abs() < 0.005,
+        _ => expected.eq_ignore_ascii_case(&actual),
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(tr!("script.mismatch", what, expected, actual))
+    }
+}
+
+fn apartment<'a>(manager: &'a Manager, number: &str) -> Result<&'a Apartment, String> {
+    number
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| manager.game_flow.hotel.apartments.get(number))
+        .ok_or(tr!("script.no_apartment", number))
+}
+
+/// Checks one assertion, e.g. `state Playing`, `capital 9000`, `day 2`, `vacant 4`
+/// or `resident 3 status Dead` (also `name`, `age`, `balance`, `role` and `type`).
+pub fn check(manager: &Manager, expectation: &[&str]) -> Result<(), String> {
+    let game_flow = &manager.game_flow;
+    match expectation {
+        ["state", name] => compare("state", name, manager.state().name().to_string()),
+        ["capital", amount] => compare("capital", amount, game_flow.hotel.capital.to_string()),
+        ["day", day] => compare("day", day, game_flow.game_history.day.to_string()),
+        ["vacant", number] => match &apartment(manager, number)?.resident {
+            None => Ok(()),
+            Some(resident) => Err(tr!(
+                "script.occupied",
+                number,
+                resident.lock().unwrap().name
+            )),
+        },
+        ["resident", number, field, expected] => {
+            let apartment = apartment(manager, number)?;
+            let Some(resident) = &apartment.resident else {
+                return Err(tr!("script.vacant", apartment.number));
+            };
+            let resident = resident.lock().unwrap();
+            let actual = match *field {
+                "name" => resident.name.clone(),
+                "age" => resident.age.to_string(),
+                "balance" => resident.account_balance.to_string(),
+                "status" => format!("{:?}", resident.status),
+                "role" => format!("{:?}", resident.strategy.confess_role()),
+                "type" => format!("{:?}", resident.resident_type),
+                _ => return Err(tr!("script.unknown_field", field)),
+            };
+            compare(
+                &format!("resident {} {}", apartment.number, field),
+                expected,
+                actual,
+            )
+        }
+        _ => Err(tr!("script.unknown_expectation", expectation.join(" "))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_expectation_reports_its_line() {
+        let mut manager = Manager::new();
+        let script = "rooms 6\n# a comment\nexpect state SetUpHotel\nexpect capital 1\n";
+
+        let error = run(&mut manager, script).unwrap_err();
+
+        assert_eq!(error.line, 4);
+        assert_eq!(error.message, "expected capital 1, but it is 10000");
+    }
+
+    #[test]
+    fn test_scenario_without_answers_stops_at_the_move() {
+        let mut manager = Manager::new();
+        let script = "rooms 2\nrps 2\nhotel set\nadd resident Aa 30 100 0\nresidents settled\n\
+                      flow alphabetical\nplay\nmove\n";
+
+        let error = run(&mut manager, script).unwrap_err();
+
+        assert_eq!(error.line, 8);
+        assert_eq!(
+            error.message,
+            "the script ended while the command waited for an answer"
+        );
+    }
+
+    #[test]
+    fn test_scenarios() {
+        let mut paths: Vec<_> = fs::read_dir("inputs/scenarios")
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty());
+        for path in paths {
+            let mut manager = Manager::new();
+            let path = path.to_str().unwrap();
+            if let Err(error) = run_file(&mut manager, path) {
+                panic!("{} failed at {}", path, error);
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::time::Duration;
+
+use crate::{
+    command,
+    console::{self, Channel, TcpChannel},
+    game_flow::GameFlow,
+    manager_states::{manager_state_behavior::ManagerStateBehavior, PlayingState},
+    resident::ResidentType,
+};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+// how long the lobby waits for a new connection to say which apartment it claims
+const JOIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+// commands a player may send from their own terminal; everything else stays with the host
+const PLAYER_COMMANDS: [&str; 10] = [
+    "move", "mail", "inbox", "sent", "clear", "announce", "board", "whoami", "help", "quit",
+];
+
+pub struct Server {
+    listener: TcpListener,
+    join_timeout: Duration,
+}
+
+impl Server {
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        Ok(Self {
+            listener,
+            join_timeout: JOIN_TIMEOUT,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn with_join_timeout(mut self, join_timeout: Duration) -> Self {
+        self.join_timeout = join_timeout;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Waits for every human resident to connect, then runs the game until all of them leave.
+    pub fn run(&self, game_flow: &mut GameFlow) -> io::Result<()> {
+        let humans = Self::human_apartments(game_flow);
+        if humans.is_empty() {
+            println!("{}", tr!("server.no_humans"));
+            return Ok(());
+        }
+        self.lobby(game_flow, &humans)?;
+        game_flow.initialize();
+        console::broadcast(&tr!("server.everyone_here"));
+        self.play(game_flow);
+        println!("{}", tr!("server.all_left"));
+        Ok(())
+    }
+
+    fn human_apartments(game_flow: &GameFlow) -> Vec<usize> {
+        game_flow
+            .hotel
+            .get_all_residents()
+            .iter()
+            .filter_map(|resident| {
+                let resident = resident.lock().unwrap();
+                if resident.resident_type == ResidentType::Human {
+                    Some(resident.apartment_number)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn lobby(&self, game_flow: &GameFlow, humans: &[usize]) -> io::Result<()> {
+        println!(
+            "{}",
+            tr!(
+                "server.waiting",
+                self.local_addr()?,
+                format!("{:?}", humans)
+            )
+        );
+        while console::registered_count() < humans.len() {
+            let (stream, address) = self.listener.accept()?;
+            // one client that fails or keeps silent must not hold up the others
+            if let Err(error) = self.admit(game_flow, humans, stream, address) {
+                println!("{}", tr!("server.dropped", address, error));
+            }
+        }
+        Ok(())
+    }
+
+    /// Lets a new connection claim one of the free apartments, if it does so in time.
+    fn admit(
+        &self,
+        game_flow: &GameFlow,
+        humans: &[usize],
+        stream: TcpStream,
+        address: SocketAddr,
+    ) -> io::Result<()> {
+        stream.set_read_timeout(Some(self.join_timeout))?;
+        let mut channel = TcpChannel::new(stream.try_clone()?)?;
+        let free: Vec<usize> = humans
+            .iter()
+            .copied()
+            .filter(|&apartment| !console::is_registered(apartment))
+            .collect();
+        channel.write(&format!(
+            "{}\n",
+            tr!("server.welcome", game_flow.hotel.id, format!("{:?}", free))
+        ))?;
+
+        let line = channel.read_line()?.unwrap_or_default();
+        let input: Vec<&str> = line.split_whitespace().collect();
+        let apartment = match input.as_slice() {
+            ["join", number] => number.parse::<usize>().ok(),
+            _ => None,
+        };
+        match apartment.filter(|apartment| free.contains(apartment)) {
+            Some(apartment) => {
+                let resident = game_flow.hotel.apartments[apartment]
+                    .resident
+                    .as_ref()
+                    .unwrap()
+                    .lock()
+                    .unwrap();
+                channel.write(&format!(
+                    "{}\n",
+                    tr!("server.you_are", resident.name, apartment)
+                ))?;
+                // once in, a player may take as long as they like over their turn
+                stream.set_read_timeout(None)?;
+                println!("{}", tr!("server.joined", address, resident.name));
+                console::register(apartment, Box::new(channel));
+            }
+            None => {
+                channel.write(&format!("{}\n", tr!("server.unclaimable")))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn play(&self, game_flow: &mut GameFlow) {
+        let mut playing_state = PlayingState;
+        let mut prompted = None;
+        while console::registered_count() > 0 {
+            let (apartment, name, is_human) = {
+                let resident = game_flow.residents[game_flow.current_moving_player]
+                    .lock()
+                    .unwrap();
+                (
+                    resident.apartment_number,
+                    resident.name.clone(),
+                    resident.resident_type == ResidentType::Human,
+                )
+            };
+            if !is_human || !console::is_registered(apartment) {
+                playing_state.handle_command(game_flow, &["move"]);
+                continue;
+            }
+            let previous_focus = console::focus(Some(apartment));
+            if prompted != Some(game_flow.current_moving_player) {
+                say!("{}", tr!("server.your_turn", name));
+                prompted = Some(game_flow.current_moving_player);
+            }
+            console::write("    => ");
+            match console::read_line_from(apartment) {
+                Some(line) => {
+                    let tokens = command::tokenize(&line).unwrap_or_else(|error| {
+                        say!("{}", error);
+                        vec![]
+                    });
+                    let input: Vec<&str> = tokens.iter().map(String::as_str).collect();
+                    match input.first() {
+                        None => {}
+                        Some(&"quit") => {
+                            console::unregister(apartment);
+                            self.hand_over_to_bot(game_flow, apartment);
+                        }
+                        Some(command) if PLAYER_COMMANDS.contains(command) => {
+                            playing_state.handle_command(game_flow, &input);
+                        }
+                        Some(_) => say!("{}", tr!("server.not_for_players")),
+                    }
+                }
+                None => {
+                    println!("{}", tr!("server.disconnected", name));
+                    self.hand_over_to_bot(game_flow, apartment);
+                }
+            }
+            console::focus(previous_focus);
+        }
+    }
+
+    fn hand_over_to_bot(&self, game_flow: &mut GameFlow, apartment: usize) {
+        if let Some(resident) = &game_flow.hotel.apartments[apartment].resident {
+            let mut resident = resident.lock().unwrap();
+            resident.resident_type = ResidentType::Bot;
+            println!("{}", tr!("server.bot_takes_over", resident.name));
+        }
+    }
+}
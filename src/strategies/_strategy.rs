@@ -1,10 +1,9 @@
 use crate::{
-    game_history,
+    console, game_history,
     hotel::Hotel,
     resident::{Resident, ResidentType},
     roles::Role,
 };
-use std::io::{self, Write};
 
 pub trait ResidentStrategy: Send + Sync {
     fn perform_action(
@@ -36,34 +35,55 @@ pub trait ResidentStrategy: Send + Sync {
 
     fn confess_role(&self) -> Role;
 
-    fn choose_target(&self, own_apartment: usize, hotel: &mut Hotel) -> usize {
-        let available_apartments = hotel.available_rooms();
-        println!(
-            "Available apartments are: {}",
-            available_apartments
-                .iter()
-                .map(|apt| apt.to_string())
-                .collect::<Vec<String>>()
-                .join(", ")
+    fn can_forge_mail(&self) -> bool {
+        false
+    }
+
+    /// Whether their move takes them to the target's apartment for the rest of the night.
+    fn leaves_home(&self) -> bool {
+        true
+    }
+
+    /// The apartment to visit, or `None` if the player's input ended before they chose.
+    fn choose_target(&self, visitor: &Resident, hotel: &mut Hotel) -> Option<usize> {
+        let own_apartment = visitor.apartment_number;
+        let available_apartments = hotel.get_ready_apartments(Some(own_apartment), &visitor.keys);
+        if let Some(target) = console::pick_target(hotel, &available_apartments) {
+            return Some(target);
+        }
+        say!(
+            "{}",
+            tr!(
+                "target.available",
+                available_apartments
+                    .iter()
+                    .map(|apt| apt.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
         );
         self.get_user_input(available_apartments, own_apartment)
     }
 
-    fn get_user_input(&self, available_apartments: Vec<usize>, own_apartment: usize) -> usize {
+    fn get_user_input(
+        &self,
+        available_apartments: Vec<usize>,
+        own_apartment: usize,
+    ) -> Option<usize> {
         loop {
-            let mut input = String::new();
-            print!("Choose an apartment number: ");
-            io::stdout().flush().unwrap();
-            io::stdin().read_line(&mut input).ok();
+            let Some(input) = console::prompt(&tr!("target.choose")) else {
+                say!("{}", tr!("input.ended"));
+                return None;
+            };
             match input.trim().parse::<usize>() {
                 Ok(number) => {
                     if available_apartments.contains(&number) && number != own_apartment {
-                        return number;
+                        return Some(number);
                     } else {
-                        println!("No such apartment available.");
+                        say!("{}", tr!("target.unavailable"));
                     }
                 }
-                Err(_) => println!("Invalid input. Please enter a valid apartment number."),
+                Err(_) => say!("{}", tr!("target.invalid")),
             }
         }
     }
@@ -1,11 +1,11 @@
 use rand::seq::SliceRandom;
-use std::io::{self, Write};
 use strum_macros::EnumIter;
 
 use super::_strategy::ResidentStrategy;
+use crate::console;
 use crate::game_history::GameHistory;
 use crate::resident::{Resident, SuperStatus};
-use crate::{hotel::Hotel, resident::Status, roles::Role};
+use crate::{hotel::Hotel, random, roles::Role};
 
 #[derive(EnumIter, Debug, Clone)]
 pub enum AvengerAction {
@@ -21,47 +21,57 @@ impl AvengerStrategy {
         avenger_apartment: usize,
         target: usize,
         history: &GameHistory,
-    ) -> AvengerAction {
+    ) -> Option<AvengerAction> {
         loop {
-            println!("Choose an action from available options:");
-            println!("1: Sleep");
+            say!("{}", tr!("action.choose"));
+            say!("{}", tr!("action.option", 1, tr!("action.Sleep")));
             if history.has_visited(avenger_apartment, target) {
-                println!("2: Kill");
+                say!("{}", tr!("action.option", 2, tr!("action.Kill")));
             }
 
-            let mut input = String::new();
-            print!("Enter the number of your chosen action: ");
-            io::stdout().flush().unwrap();
-            io::stdin()
-                .read_line(&mut input)
-                .ok()
-                .expect("Failed to read line");
+            let Some(input) = console::prompt(&tr!("action.enter_number")) else {
+                say!("{}", tr!("input.ended"));
+                return None;
+            };
 
             match input.trim().parse::<usize>() {
-                Ok(1) => return AvengerAction::Sleep,
+                Ok(1) => return Some(AvengerAction::Sleep),
                 Ok(2) if history.has_visited(avenger_apartment, target) => {
-                    return AvengerAction::Kill
+                    return Some(AvengerAction::Kill)
                 }
-                _ => println!("Invalid choice, please try again."),
+                _ => say!("{}", tr!("action.invalid")),
             }
         }
     }
 
-    fn perform_avenger_action(&self, action: AvengerAction, hotel: &mut Hotel, target: usize) {
+    /// Returns whoever the Avenger killed.
+    fn perform_avenger_action(
+        &self,
+        action: AvengerAction,
+        hotel: &mut Hotel,
+        target: usize,
+    ) -> Option<usize> {
         match action {
             AvengerAction::Sleep => {
-                if let Some(resident) = &hotel.apartments[target].resident {
+                let Some(sleeper) = hotel.someone_in(target) else {
+                    say!("{}", tr!("avenger.finds_nobody", target));
+                    return None;
+                };
+                if let Some(resident) = &hotel.apartments[sleeper].resident {
                     let mut resident = resident.lock().unwrap();
                     resident.super_status = SuperStatus::Asleep;
                 }
-                println!("Avenger puts the resident in apartment {} to sleep", target);
+                say!("{}", tr!("avenger.sleeps", sleeper));
+                None
             }
             AvengerAction::Kill => {
-                if let Some(resident) = &hotel.apartments[target].resident {
-                    let mut resident = resident.lock().unwrap();
-                    resident.status = Status::Dead;
+                let victim = hotel.attack(target);
+                match victim {
+                    Some(victim) if victim == target => say!("{}", tr!("avenger.kills", target)),
+                    Some(victim) => say!("{}", tr!("avenger.kills_guest", victim, target)),
+                    None => say!("{}", tr!("avenger.finds_nobody", target)),
                 }
-                println!("Avenger kills the resident in apartment {}", target);
+                victim
             }
         }
     }
@@ -75,14 +85,18 @@ impl ResidentStrategy for AvengerStrategy {
         history: &mut GameHistory,
     ) {
         let avenger_apartment = performer.apartment_number;
-        let target = self.choose_target(avenger_apartment, hotel);
-        let action = self.choose_action(avenger_apartment, target, history);
-        self.perform_avenger_action(action.clone(), hotel, target);
-        history.add_action(
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
+        let Some(action) = self.choose_action(avenger_apartment, target, history) else {
+            return;
+        };
+        let victim = self.perform_avenger_action(action.clone(), hotel, target);
+        history.add_attack(
             avenger_apartment,
             std::format!("{:?}", action),
             target,
-            None,
+            victim,
         );
     }
 
@@ -94,24 +108,23 @@ impl ResidentStrategy for AvengerStrategy {
     ) {
         let avenger_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(avenger_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(avenger_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
             let action = if history.has_visited(avenger_apartment, *target) {
                 AvengerAction::Kill
             } else {
                 AvengerAction::Sleep
             };
-            self.perform_avenger_action(action.clone(), hotel, *target);
-            history.add_action(
+            let victim = self.perform_avenger_action(action.clone(), hotel, *target);
+            history.add_attack(
                 avenger_apartment,
                 std::format!("{:?}", action),
                 *target,
-                None,
+                victim,
             );
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
         }
     }
 
@@ -2,6 +2,7 @@ use super::_strategy::ResidentStrategy;
 use crate::{
     game_history::GameHistory,
     hotel::Hotel,
+    random,
     resident::{Resident, SuperStatus},
     roles::Role,
 };
@@ -11,22 +12,20 @@ pub struct DoctorStrategy;
 
 impl DoctorStrategy {
     fn heal(&self, hotel: &mut Hotel, target: usize) {
-        if let Some(resident) = &hotel.apartments[target].resident {
+        let Some(patient) = hotel.someone_in(target) else {
+            say!("{}", tr!("visit.nobody", target));
+            return;
+        };
+        if let Some(resident) = &hotel.apartments[patient].resident {
             let mut resident = resident.lock().unwrap();
             match resident.super_status {
                 SuperStatus::Drugged => {
                     resident.super_status = SuperStatus::Overdosed;
-                    println!(
-                        "Doctor heals the resident in apartment {}. They are now Overdosed.",
-                        target
-                    );
+                    say!("{}", tr!("doctor.overdoses", patient));
                 }
                 _ => {
                     resident.super_status = SuperStatus::Drugged;
-                    println!(
-                        "Doctor heals the resident in apartment {}. They are now Drugged.",
-                        target
-                    );
+                    say!("{}", tr!("doctor.drugs", patient));
                 }
             }
         }
@@ -41,7 +40,9 @@ impl ResidentStrategy for DoctorStrategy {
         history: &mut GameHistory,
     ) {
         let doctor_apartment = performer.apartment_number;
-        let target = self.choose_target(doctor_apartment, hotel);
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
         self.heal(hotel, target);
         history.add_action(doctor_apartment, "Heal".to_string(), target, None);
     }
@@ -54,14 +55,13 @@ impl ResidentStrategy for DoctorStrategy {
     ) {
         let doctor_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(doctor_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(doctor_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
             self.heal(hotel, *target);
             history.add_action(doctor_apartment, "Heal".to_string(), *target, None);
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
         }
     }
 
@@ -1,22 +1,28 @@
 use super::_strategy::ResidentStrategy;
-use crate::{game_history::GameHistory, hotel::Hotel, resident::Resident, roles::Role};
+use crate::{
+    document, game_history::GameHistory, hotel::Hotel, random, resident::Resident, roles::Role,
+};
 use rand::seq::SliceRandom;
 
 pub struct JanitorStrategy;
 
 impl JanitorStrategy {
-    fn clean(&self, hotel: &mut Hotel, target: usize) {
-        println!("Janitor cleans the resident's apartment {}", target);
+    fn clean(&self, hotel: &mut Hotel, target: usize, janitor: &mut Resident) {
+        say!("{}", tr!("janitor.cleans", target));
+        if !janitor.keys.contains(&target) {
+            say!("{}", tr!("janitor.copies_key", target));
+            janitor.keys.push(target);
+        }
 
         if let Some(apartment) = hotel.apartments.get_mut(target) {
             // See the documents of the resident whose apartment_number is the target
             if let Some(resident) = &apartment.resident {
                 let resident = resident.lock().unwrap();
-                println!("Documents of the resident in apartment {}:", target);
-                println!("{:?}", resident.documents);
+                say!("{}", tr!("janitor.documents", target));
+                say!("{}", document::list(&resident.documents));
             }
-            // lock the apartment
-            apartment.is_opened = false;
+            // seal the apartment for good; only key holders get in from now on
+            apartment.is_sealed = true;
         }
     }
 }
@@ -29,8 +35,10 @@ impl ResidentStrategy for JanitorStrategy {
         history: &mut GameHistory,
     ) {
         let janitor_apartment = performer.apartment_number;
-        let target = self.choose_target(janitor_apartment, hotel);
-        self.clean(hotel, target);
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
+        self.clean(hotel, target, performer);
         history.add_action(janitor_apartment, "Clean".to_string(), target, None);
     }
 
@@ -42,14 +50,13 @@ impl ResidentStrategy for JanitorStrategy {
     ) {
         let janitor_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(janitor_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(janitor_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
-            self.clean(hotel, *target);
+            self.clean(hotel, *target, performer);
             history.add_action(janitor_apartment, "Clean".to_string(), *target, None);
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
         }
     }
 
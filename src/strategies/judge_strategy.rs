@@ -1,13 +1,14 @@
-use std::io;
-
 use super::_strategy::ResidentStrategy;
 use crate::{
+    console,
     game_history::{self, GameHistory},
     hotel::Hotel,
     mail::Suspicion,
+    random,
     resident::Resident,
     roles::Role,
 };
+use rand::Rng;
 
 pub struct JudgeStrategy;
 
@@ -21,9 +22,9 @@ impl JudgeStrategy {
     ) {
         if vote_for {
             suspicion.for_votes += 1;
-            println!(
-                "Judge from apartment {} votes for the arrest of the resident in apartment {}",
-                judge_apartment, suspicion.suspected
+            say!(
+                "{}",
+                tr!("judge.votes_for", judge_apartment, suspicion.suspected)
             );
             history.add_action(
                 judge_apartment,
@@ -33,9 +34,9 @@ impl JudgeStrategy {
             );
         } else {
             suspicion.against_votes += 1;
-            println!(
-                "Judge from apartment {} votes against the arrest of the resident in apartment {}",
-                judge_apartment, suspicion.suspected
+            say!(
+                "{}",
+                tr!("judge.votes_against", judge_apartment, suspicion.suspected)
             );
             history.add_action(judge_apartment, "Vote against".to_string(), 0, None);
         }
@@ -51,14 +52,11 @@ impl ResidentStrategy for JudgeStrategy {
     ) {
         let judge_apartment = performer.apartment_number;
         for (target, suspicion) in hotel.investigation_queue.iter_mut() {
-            println!(
-                "Do you vote for the arrest in apartment {}? ('+' if so)",
-                target
-            );
-            let mut vote_input = String::new();
-            io::stdin()
-                .read_line(&mut vote_input)
-                .expect("Failed to read line");
+            say!("{}", tr!("judge.ask", target));
+            let Some(vote_input) = console::read_line() else {
+                say!("{}", tr!("input.ended"));
+                break;
+            };
 
             let vote_for = vote_input.trim().to_lowercase() == "+";
             self.vote(suspicion, judge_apartment, vote_for, history);
@@ -74,7 +72,7 @@ impl ResidentStrategy for JudgeStrategy {
     ) {
         let judge_apartment = performer.apartment_number;
         for (_, suspicion) in hotel.investigation_queue.iter_mut() {
-            let vote_for = rand::random::<f32>() > 0.2; // biased
+            let vote_for = random::rng().gen::<f32>() > 0.2; // biased
             self.vote(suspicion, judge_apartment, vote_for, history);
         }
     }
@@ -82,4 +80,8 @@ impl ResidentStrategy for JudgeStrategy {
     fn confess_role(&self) -> Role {
         Role::Judge
     }
+
+    fn leaves_home(&self) -> bool {
+        false // votes from their own apartment
+    }
 }
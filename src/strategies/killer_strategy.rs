@@ -1,14 +1,10 @@
-use std::io::{self, Write};
-
 use rand::seq::SliceRandom;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use super::_strategy::ResidentStrategy;
 use crate::{
-    game_history::GameHistory,
-    hotel::Hotel,
-    resident::{Resident, Status},
+    console, game_history::GameHistory, hotel::Hotel, mail::Mail, random, resident::Resident,
     roles::Role,
 };
 
@@ -23,28 +19,28 @@ pub enum KillerAction {
 pub struct KillerStrategy;
 
 impl KillerStrategy {
-    fn choose_action(&self) -> KillerAction {
+    fn choose_action(&self) -> Option<KillerAction> {
         loop {
-            println!("Choose an action from available options:");
+            say!("{}", tr!("action.choose"));
             for (i, action) in KillerAction::iter().enumerate() {
-                println!("{}: {:?}", i + 1, action);
+                say!(
+                    "{}",
+                    tr!("action.option", i + 1, tr!(&format!("action.{:?}", action)))
+                );
             }
 
-            let mut input = String::new();
-            print!("Enter the number of your chosen action: ");
-            io::stdout().flush().unwrap();
-            io::stdin()
-                .read_line(&mut input)
-                .ok()
-                .expect("Failed to read line");
+            let Some(input) = console::prompt(&tr!("action.enter_number")) else {
+                say!("{}", tr!("input.ended"));
+                return None;
+            };
 
             match input.trim().parse::<usize>() {
                 Ok(index) if index > 0 && index <= KillerAction::iter().count() => {
                     if let Some(action) = KillerAction::iter().nth(index - 1) {
-                        return action;
+                        return Some(action);
                     }
                 }
-                _ => println!("Invalid choice, please try again."),
+                _ => say!("{}", tr!("action.invalid")),
             }
         }
     }
@@ -55,30 +51,36 @@ impl KillerStrategy {
         hotel: &mut Hotel,
         target: usize,
         killer: &mut Resident,
-    ) {
+        day: usize,
+        threat: String,
+    ) -> Option<usize> {
         match action {
             KillerAction::Kill => {
-                if let Some(resident) = &hotel.apartments[target].resident {
-                    let mut resident = resident.lock().unwrap();
-                    resident.status = Status::Dead;
+                let victim = hotel.attack(target);
+                match victim {
+                    Some(victim) if victim == target => say!("{}", tr!("killer.kills", target)),
+                    Some(victim) => say!("{}", tr!("killer.kills_guest", victim, target)),
+                    None => say!("{}", tr!("killer.finds_nobody", target)),
                 }
-                println!("Killer kills the resident in apartment {}", target);
+                return victim;
             }
             KillerAction::Threaten => {
-                println!("Killer threatens the resident in apartment {}", target);
-                let mut mail = String::new();
-                println!("Please, write the mail to the resident from the apartment:");
-                io::stdin().read_line(&mut mail).unwrap();
-                hotel.apartments[target].mails.push(mail);
+                say!("{}", tr!("killer.threatens", target));
+                let mail = Mail::new(killer.apartment_number, target, threat, day).anonymous();
+                hotel.send_mail(mail);
                 // todo: Implement a bit more useful threaten logic (to enforce to action)
             }
             KillerAction::Bribe => {
-                println!("Killer bribes the resident in apartment {}", target);
+                say!("{}", tr!("killer.bribes", target));
                 // Implement the bribe logic
             }
             KillerAction::Rob => {
-                println!("Killer robs the resident in apartment {}", target);
-                if let Some(target_resident) = &hotel.apartments[target].resident {
+                let Some(robbed) = hotel.someone_in(target) else {
+                    say!("{}", tr!("killer.finds_nobody", target));
+                    return None;
+                };
+                say!("{}", tr!("killer.robs", robbed));
+                if let Some(target_resident) = &hotel.apartments[robbed].resident {
                     let mut res = target_resident.lock().unwrap();
                     let money = res.account_balance;
                     res.account_balance = 0.0;
@@ -87,6 +89,7 @@ impl KillerStrategy {
                 // Implement the rob logic
             }
         }
+        None
     }
 }
 
@@ -98,10 +101,37 @@ impl ResidentStrategy for KillerStrategy {
         history: &mut GameHistory,
     ) {
         let killer_apartment = performer.apartment_number;
-        let target = self.choose_target(killer_apartment, hotel);
-        let action = self.choose_action();
-        self.perform_killer_action(action.clone(), hotel, target, performer);
-        history.add_action(killer_apartment, std::format!("{:?}", action), target, None);
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
+        let Some(action) = self.choose_action() else {
+            return;
+        };
+        // the threat is written before anything happens, so running out of input takes no move
+        let threat = match action {
+            KillerAction::Threaten => {
+                let Some(threat) = console::prompt(&tr!("killer.write_threat")) else {
+                    say!("{}", tr!("input.ended"));
+                    return;
+                };
+                threat
+            }
+            _ => String::new(),
+        };
+        let victim = self.perform_killer_action(
+            action.clone(),
+            hotel,
+            target,
+            performer,
+            history.day,
+            threat,
+        );
+        history.add_attack(
+            killer_apartment,
+            std::format!("{:?}", action),
+            target,
+            victim,
+        );
     }
 
     fn perform_action_bot(
@@ -112,20 +142,26 @@ impl ResidentStrategy for KillerStrategy {
     ) {
         let killer_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(killer_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(killer_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
             let action = KillerAction::Kill; // Bots always choose to kill, change as needed
-            self.perform_killer_action(action.clone(), hotel, *target, performer);
-            history.add_action(
+            let victim = self.perform_killer_action(
+                action.clone(),
+                hotel,
+                *target,
+                performer,
+                history.day,
+                String::new(),
+            );
+            history.add_attack(
                 killer_apartment,
                 std::format!("{:?}", action),
                 *target,
-                None,
+                victim,
             );
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
         }
     }
 
@@ -133,3 +169,25 @@ impl ResidentStrategy for KillerStrategy {
         Role::Killer
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::BufferChannel;
+
+    #[test]
+    fn test_a_threat_left_unwritten_takes_no_move() {
+        let mut hotel = Hotel::for_test(2, 2, &[("Kim", Role::Killer), ("Dan", Role::Doctor)]);
+        let killer = hotel.apartments[0].resident.clone().unwrap();
+        let mut history = GameHistory::new();
+        // the target and the threat are chosen, then the input ends
+        let host = BufferChannel::new(vec!["1".to_string(), "4".to_string()]);
+        let previous = console::set_host(Some(Box::new(host)));
+
+        KillerStrategy.perform_action_human(&mut killer.lock().unwrap(), &mut hotel, &mut history);
+        console::set_host(previous);
+
+        assert!(history.actions.is_empty());
+        assert!(hotel.apartments[1].mails.is_empty());
+    }
+}
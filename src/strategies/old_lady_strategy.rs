@@ -1,27 +1,22 @@
 use super::_strategy::ResidentStrategy;
-use crate::{game_history::GameHistory, hotel::Hotel, resident::Resident, roles::Role};
+use crate::{
+    document, game_history::GameHistory, hotel::Hotel, random, resident::Resident, roles::Role,
+};
 use rand::seq::SliceRandom;
 
 pub struct OldLadyStrategy;
 
 impl OldLadyStrategy {
-    fn pay_visit(&self, hotel: &mut Hotel, old_lady_apartment: usize, target: usize) {
-        println!(
-            "Old Lady pays a visit about the resident in apartment {}",
-            target
-        );
+    fn pay_visit(&self, hotel: &mut Hotel, target: usize) {
+        say!("{}", tr!("old_lady.visits", target));
 
         if let Some(apartment) = hotel.apartments.get_mut(target) {
             // Take a look at the documents of the resident
             if let Some(resident) = &apartment.resident {
                 let resident = resident.lock().unwrap();
-                println!(
-                    "Old Lady looks at the documents of the resident in apartment {}:",
-                    target
-                );
-                println!("{:?}", resident.documents);
-                println!("Old Lady has tea and falls asleep in apartment {}", target);
-                apartment.guests.push(old_lady_apartment);
+                say!("{}", tr!("old_lady.documents", target));
+                say!("{}", document::list(&resident.documents));
+                say!("{}", tr!("old_lady.falls_asleep", target));
             }
         }
     }
@@ -35,8 +30,10 @@ impl ResidentStrategy for OldLadyStrategy {
         history: &mut GameHistory,
     ) {
         let old_lady_apartment = performer.apartment_number;
-        let target = self.choose_target(old_lady_apartment, hotel);
-        self.pay_visit(hotel, old_lady_apartment, target);
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
+        self.pay_visit(hotel, target);
         history.add_action(old_lady_apartment, "pay_visit".to_string(), target, None);
     }
 
@@ -48,14 +45,13 @@ impl ResidentStrategy for OldLadyStrategy {
     ) {
         let old_lady_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(old_lady_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(old_lady_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
-            self.pay_visit(hotel, old_lady_apartment, *target);
+            self.pay_visit(hotel, *target);
             history.add_action(old_lady_apartment, "Pay visit".to_string(), *target, None);
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
         }
     }
 
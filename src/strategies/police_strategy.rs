@@ -1,32 +1,53 @@
 use super::_strategy::ResidentStrategy;
 use crate::{
-    game_history::GameHistory, hotel::Hotel, mail::Suspicion, resident::Resident, roles::Role,
+    document, game_history::GameHistory, hotel::Hotel, mail::Suspicion, random, resident::Resident,
+    roles::Role,
 };
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
+
+/// How likely the Police are to notice each flaw of a document.
+const NOTICE_CHANCE: f64 = 0.6;
 
 pub struct PoliceStrategy;
 
 impl PoliceStrategy {
     fn investigate(&self, hotel: &mut Hotel, police_apartment: usize, target: usize) {
-        println!("Police investigates the resident in apartment {}", target);
+        say!("{}", tr!("police.investigates", target));
+        let Some(target) = hotel.someone_in(target) else {
+            say!("{}", tr!("visit.nobody", target));
+            return;
+        };
 
         if let Some(apartment) = hotel.apartments.get_mut(target) {
             if let Some(resident) = &apartment.resident {
                 let resident = resident.lock().unwrap();
-                println!(
-                    "Police looks at the documents of the resident in apartment {}:",
-                    target
-                );
-                println!("{:?}", resident.documents);
+                say!("{}", tr!("police.documents", target));
+                say!("{}", document::list(&resident.documents));
+
+                // Hold every document up against its holder
+                let mut rng = random::rng();
+                let mut flaw_found = false;
+                for document in &resident.documents {
+                    // a genuine document can only be wrong for its holder: it was stolen
+                    let finding = if document.forged {
+                        "police.forgery"
+                    } else {
+                        "police.stolen"
+                    };
+                    for flaw in document.flaws(&resident.face) {
+                        if rng.gen_bool(NOTICE_CHANCE) {
+                            say!("{}", tr!(finding, document.role, target, flaw));
+                            flaw_found = true;
+                        }
+                    }
+                }
 
-                let mut is_suspicious = if resident.documents.is_empty() {
+                let mut is_suspicious = if flaw_found {
+                    true
+                } else if resident.documents.is_empty() {
                     false
                 } else if resident.documents.len() == 1 {
-                    let document_role = resident.documents[0].role;
-                    match document_role {
-                        Role::Killer | Role::Swindler => true,
-                        _ => false,
-                    }
+                    matches!(resident.documents[0].role, Role::Killer | Role::Swindler)
                 } else {
                     true // more than one document
                 };
@@ -45,12 +66,9 @@ impl PoliceStrategy {
                 }
 
                 if is_suspicious {
-                    println!("Police suspects the resident in apartment {}", target);
-                    let suspicion = Suspicion::new(
-                        police_apartment,
-                        target,
-                        "Suspicious activity detected".to_string(),
-                    );
+                    say!("{}", tr!("police.suspects", target));
+                    let suspicion =
+                        Suspicion::new(police_apartment, target, tr!("police.suspicion"));
                     hotel.investigation_queue.insert(target, suspicion);
                 }
             }
@@ -66,7 +84,9 @@ impl ResidentStrategy for PoliceStrategy {
         history: &mut GameHistory,
     ) {
         let police_apartment = performer.apartment_number;
-        let target = self.choose_target(police_apartment, hotel);
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
         self.investigate(hotel, police_apartment, target);
         history.add_action(police_apartment, "Investigate".to_string(), target, None);
     }
@@ -79,14 +99,13 @@ impl ResidentStrategy for PoliceStrategy {
     ) {
         let police_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(police_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(police_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
             self.investigate(hotel, police_apartment, *target);
             history.add_action(police_apartment, "Investigate".to_string(), *target, None);
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
         }
     }
 
@@ -94,3 +113,36 @@ impl ResidentStrategy for PoliceStrategy {
         Role::Police
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::{self, BufferChannel};
+    use crate::document::Document;
+
+    #[test]
+    fn test_investigation_shows_documents_without_telling_forgeries() {
+        let mut hotel = Hotel::for_test(2, 2, &[("Pat", Role::Police), ("Sam", Role::Swindler)]);
+        {
+            let mut swindler = hotel.apartments[1]
+                .resident
+                .as_ref()
+                .unwrap()
+                .lock()
+                .unwrap();
+            let forged = Document::forge(Role::Doctor, "Sam".to_string(), 30, swindler.face);
+            swindler.documents = vec![forged];
+        }
+        let host = BufferChannel::new(vec![]);
+        let output = host.output();
+        let previous = console::set_host(Some(Box::new(host)));
+
+        PoliceStrategy.investigate(&mut hotel, 0, 1);
+        console::set_host(previous);
+
+        let output = output.borrow();
+        assert!(output.contains("Sam"));
+        assert!(!output.contains("forged:"));
+        assert!(!output.contains("Document {"));
+    }
+}
@@ -1,13 +1,17 @@
 use rand::seq::SliceRandom;
 
 use super::_strategy::ResidentStrategy;
-use crate::{game_history::GameHistory, hotel::Hotel, resident::Resident, roles::Role};
+use crate::{game_history::GameHistory, hotel::Hotel, random, resident::Resident, roles::Role};
 
 pub struct ProfessorStrategy;
 
 impl ProfessorStrategy {
-    fn lecture(&self, _: &mut Hotel, target: usize) {
-        println!("Professor lectures the resident in apartment {}", target);
+    fn lecture(&self, hotel: &mut Hotel, target: usize) {
+        let Some(listener) = hotel.someone_in(target) else {
+            say!("{}", tr!("visit.nobody", target));
+            return;
+        };
+        say!("{}", tr!("professor.lectures", listener));
         // Implement the lecture logic
     }
 }
@@ -20,7 +24,9 @@ impl ResidentStrategy for ProfessorStrategy {
         history: &mut GameHistory,
     ) {
         let professor_apartment = performer.apartment_number;
-        let target = self.choose_target(professor_apartment, hotel);
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
         self.lecture(hotel, target);
         history.add_action(
             professor_apartment,
@@ -37,8 +43,8 @@ impl ResidentStrategy for ProfessorStrategy {
     ) {
         let professor_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(professor_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(professor_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
             self.lecture(hotel, *target);
             history.add_action(
@@ -48,8 +54,7 @@ impl ResidentStrategy for ProfessorStrategy {
                 None,
             );
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
         }
     }
 
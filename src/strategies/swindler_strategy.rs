@@ -1,13 +1,16 @@
 use super::_strategy::ResidentStrategy;
 use crate::{
+    console,
     document::Document,
     game_history::GameHistory,
     hotel::{self, Hotel},
     mail::Suspicion,
+    random,
     resident::Resident,
     roles::Role,
 };
 use rand::{seq::SliceRandom, Rng};
+use strum::IntoEnumIterator;
 
 #[derive(Debug, Clone)]
 pub enum SwindleSubstrategy {
@@ -26,8 +29,8 @@ pub struct SwindlerStrategy {
 impl SwindlerStrategy {
     pub fn new(sub_strategy: Option<SwindleSubstrategy>) -> Self {
         let sub_strategy = sub_strategy.unwrap_or_else(|| {
-            let mut rng = rand::thread_rng();
-            let strategies = vec![
+            let mut rng = random::rng();
+            let strategies = [
                 SwindleSubstrategy::InnocentLook,
                 SwindleSubstrategy::BadGuy,
                 SwindleSubstrategy::GoodGuy,
@@ -79,12 +82,10 @@ impl SwindlerStrategy {
                 target_resident.account_balance = 0.0;
                 target_resident.documents.clear();
             } else {
-                println!("No resident found in target apartment {}", apartment);
-                return;
+                say!("{}", tr!("swindler.nobody", apartment));
             }
         } else {
-            println!("Target apartment {} not found", apartment);
-            return;
+            say!("{}", tr!("swindler.no_apartment", apartment));
         }
     }
 
@@ -121,36 +122,64 @@ impl SwindlerStrategy {
         swindler.account_balance = money;
     }
 
+    /// Takes the keys the target holds, or only those a human swindler says yes to.
+    fn steal_keys(
+        &self,
+        hotel: &mut hotel::Hotel,
+        target: usize,
+        swindler: &mut Resident,
+        ask: bool,
+    ) {
+        let Some(resident) = hotel
+            .apartments
+            .get(target)
+            .and_then(|a| a.resident.as_ref())
+        else {
+            return;
+        };
+        let mut resident = resident.lock().unwrap();
+        let keys = std::mem::take(&mut resident.keys);
+        for key in keys {
+            let take = !ask || {
+                say!("{}", tr!("swindler.take_key", key));
+                let input = console::read_line()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_lowercase();
+                input == "y" || input == tr!("answer.yes")
+            };
+            if !take {
+                resident.keys.push(key);
+            } else if !swindler.keys.contains(&key) {
+                swindler.keys.push(key);
+            }
+        }
+    }
+
     fn swindle_human(&self, hotel: &mut hotel::Hotel, target: usize, swindler: &mut Resident) {
         fn ask_user_which_documents_to_take(combined_documents: &Vec<Document>) -> Vec<Document> {
             let mut take_documents: Vec<_> = vec![];
             for doc in combined_documents {
-                println!("Do you want to take this document? (y/n): {:?}", doc);
-                let mut input = String::new();
-                std::io::stdin()
-                    .read_line(&mut input)
-                    .expect("Failed to read line");
-                if input.trim().to_lowercase() == "y" {
+                say!("{}", tr!("swindler.take_document", doc));
+                let input = console::read_line()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_lowercase();
+                if input == "y" || input == tr!("answer.yes") {
                     take_documents.push(doc.clone());
                 }
             }
             take_documents
         }
         fn ask_user_how_much_money_to_take(combined_money: &f64) -> f64 {
-            println!(
-                "How much money do you want to take? (Enter a number between 0 and {}):",
-                combined_money
-            );
-            let mut input = String::new();
-            std::io::stdin()
-                .read_line(&mut input)
-                .expect("Failed to read line");
+            say!("{}", tr!("swindler.take_money", combined_money));
+            let input = console::read_line().unwrap_or_default();
             let take_money: f64 = input.trim().parse().unwrap_or(0.0);
-            let take_money = take_money.min(*combined_money);
-            take_money
+
+            take_money.min(*combined_money)
         }
 
-        println!("Swindler swindles the resident in apartment {}", target);
+        say!("{}", tr!("swindler.swindles", target));
         let mut combined_documents: Vec<Document> = vec![];
         let mut combined_money = 0.0;
 
@@ -177,10 +206,36 @@ impl SwindlerStrategy {
 
         self.update_resident(hotel, target, resident_documents, resident_money);
         self.update_swindler(swindler, take_documents, take_money);
+        self.steal_keys(hotel, target, swindler, true);
+    }
+
+    /// Asks the human swindler which role to fake tonight, if any.
+    fn ask_role_to_forge() -> Option<Role> {
+        say!("{}", tr!("swindler.forge"));
+        for (i, role) in Role::iter().enumerate() {
+            say!("{}", tr!("action.option", i + 1, role));
+        }
+        let input = console::read_line()?;
+        input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| Role::iter().nth(index))
+    }
+
+    fn forge(&self, swindler: &mut Resident, role: Role, history: &mut GameHistory) {
+        say!("{}", tr!("swindler.forges", role));
+        let mut document =
+            Document::forge(role, swindler.name.clone(), swindler.age, swindler.face);
+        document.traits = swindler.traits.clone();
+        swindler.documents.push(document);
+        let apartment = swindler.apartment_number;
+        history.add_action(apartment, "Forge".to_string(), apartment, None);
     }
 
     fn swindle_bot(&self, hotel: &mut hotel::Hotel, target: usize, swindler: &mut Resident) {
-        println!("Swindler swindles the resident in apartment {}", target);
+        say!("{}", tr!("swindler.swindles", target));
         let mut combined_documents: Vec<Document> = vec![];
         let mut combined_money = 0.0;
         let mut take_documents: Vec<Document> = vec![];
@@ -247,10 +302,7 @@ impl SwindlerStrategy {
                             let suspicion = Suspicion {
                                 from: swindler.apartment_number,
                                 suspected: target,
-                                description: format!(
-                                    "Swindler informs about a killer in apartment {}",
-                                    target
-                                ),
+                                description: tr!("swindler.informs", target),
                                 for_votes: 0,
                                 against_votes: 0,
                             };
@@ -266,7 +318,7 @@ impl SwindlerStrategy {
                 }
                 SwindleSubstrategy::Random => {
                     // Randomly choose documents to take
-                    let mut rng = rand::thread_rng();
+                    let mut rng = random::rng();
                     let num_documents_to_take = rng.gen_range(1..=combined_documents.len());
                     let mut taken_documents = vec![];
                     for _ in 0..num_documents_to_take {
@@ -281,7 +333,7 @@ impl SwindlerStrategy {
         take_money = if take_money > 0.0 {
             take_money
         } else {
-            combined_money * rand::random::<f64>()
+            combined_money * random::rng().gen::<f64>()
         };
 
         let left_documents = combined_documents
@@ -291,14 +343,10 @@ impl SwindlerStrategy {
             .collect();
         let left_money = combined_money - take_money;
         // Update the residents with the new documents and money
-        self.update_resident(
-            hotel,
-            target,
-            left_documents,
-            left_money,
-        );
+        self.update_resident(hotel, target, left_documents, left_money);
 
         self.update_swindler(swindler, take_documents, take_money);
+        self.steal_keys(hotel, target, swindler, false);
     }
 }
 
@@ -310,9 +358,17 @@ impl ResidentStrategy for SwindlerStrategy {
         history: &mut GameHistory,
     ) {
         let swindler_apartment = performer.apartment_number;
-        let target = self.choose_target(swindler_apartment, hotel);
-        self.swindle_human(hotel, target, performer);
+        let Some(target) = self.choose_target(performer, hotel) else {
+            return;
+        };
+        match hotel.someone_in(target) {
+            Some(victim) => self.swindle_human(hotel, victim, performer),
+            None => say!("{}", tr!("visit.nobody", target)),
+        }
         history.add_action(swindler_apartment, "Swindle".to_string(), target, None);
+        if let Some(role) = SwindlerStrategy::ask_role_to_forge() {
+            self.forge(performer, role, history);
+        }
     }
 
     fn perform_action_bot(
@@ -323,18 +379,36 @@ impl ResidentStrategy for SwindlerStrategy {
     ) {
         let swindler_apartment = performer.apartment_number;
         if let Some(target) = hotel
-            .get_ready_apartments(Some(swindler_apartment))
-            .choose(&mut rand::thread_rng())
+            .get_ready_apartments(Some(swindler_apartment), &performer.keys)
+            .choose(&mut random::rng())
         {
-            self.swindle_bot(hotel, *target, performer);
+            match hotel.someone_in(*target) {
+                Some(victim) => self.swindle_bot(hotel, victim, performer),
+                None => say!("{}", tr!("visit.nobody", target)),
+            }
             history.add_action(swindler_apartment, "Swindle".to_string(), *target, None);
         } else {
-            println!("No available apartments to perform action");
-            return;
+            say!("{}", tr!("action.none_available"));
+        }
+
+        // Without innocent papers, fake some now and then
+        let mut rng = random::rng();
+        let good_roles = SwindlerStrategy::good_roles();
+        let looks_innocent = performer
+            .documents
+            .iter()
+            .any(|document| good_roles.contains(&document.role));
+        if !looks_innocent && rng.gen_bool(0.5) {
+            let role = *good_roles.choose(&mut rng).unwrap();
+            self.forge(performer, role, history);
         }
     }
 
     fn confess_role(&self) -> Role {
         Role::Swindler
     }
+
+    fn can_forge_mail(&self) -> bool {
+        true
+    }
 }
@@ -1,15 +1,167 @@
-pub fn format_to_length(s: &String, length: usize) -> String {
-    if s.len() >= length {
-        s.chars().take(length).collect()
-    } else {
-        let padding = length - s.len();
-        let left_padding = padding / 2;
-        let right_padding = padding - left_padding;
-        format!(
-            "{}{}{}",
-            " ".repeat(left_padding),
-            s,
-            " ".repeat(right_padding)
-        )
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ELLIPSIS: char = '…';
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Number of terminal columns `s` takes (an emoji takes two).
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Cuts `s` to at most `width` columns, marking the cut with an ellipsis.
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut output = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > width - 1 {
+            break;
+        }
+        output.push(c);
+        used += char_width;
+    }
+    output.push(ELLIPSIS);
+    output
+}
+
+/// Centres `s` in exactly `length` columns, truncating it if it doesn't fit.
+pub fn format_to_length(s: &str, length: usize) -> String {
+    let s = truncate_to_width(s, length);
+    let padding = length.saturating_sub(display_width(&s));
+    let left_padding = padding / 2;
+    let right_padding = padding - left_padding;
+    format!(
+        "{}{}{}",
+        " ".repeat(left_padding),
+        s,
+        " ".repeat(right_padding)
+    )
+}
+
+pub fn terminal_width() -> usize {
+    crossterm::terminal::size()
+        .map(|(width, _)| width as usize)
+        .ok()
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .filter(|&width| width > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+/// Widths of the fields shown in every cell of a grid. Fields are laid out two per line
+/// as `left: right`; an odd last field gets the line to itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellLayout {
+    pub widths: Vec<usize>,
+}
+
+impl CellLayout {
+    pub fn new(widths: Vec<usize>) -> Self {
+        Self { widths }
+    }
+
+    pub fn lines(&self) -> usize {
+        self.widths.len().div_ceil(2)
+    }
+
+    fn line_width(pair: &[usize]) -> usize {
+        match pair {
+            [left, right] => left + 2 + right,
+            [single] => *single,
+            _ => 0,
+        }
+    }
+
+    /// Width of a cell without its borders.
+    pub fn inner_width(&self) -> usize {
+        self.widths
+            .chunks(2)
+            .map(Self::line_width)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Narrows the widest fields until `cells` cells and `extra` columns fit in `available` columns.
+    pub fn fit(mut self, cells: usize, extra: usize, available: usize) -> Self {
+        let cells = cells.max(1);
+        let max_inner = (available.saturating_sub(extra) / cells).saturating_sub(2);
+        while self.inner_width() > max_inner {
+            let widest_line = self
+                .widths
+                .chunks(2)
+                .enumerate()
+                .max_by_key(|(_, pair)| Self::line_width(pair))
+                .map(|(line, _)| line * 2);
+            let Some(start) = widest_line else { break };
+            let end = (start + 2).min(self.widths.len());
+            let widest = (start..end)
+                .max_by_key(|&field| self.widths[field])
+                .unwrap();
+            if self.widths[widest] <= 1 {
+                break;
+            }
+            self.widths[widest] -= 1;
+        }
+        self
+    }
+
+    /// One line of a cell, borders included.
+    pub fn render_line(&self, line: usize, fields: &[String]) -> String {
+        let inner = self.inner_width();
+        let start = line * 2;
+        let text = match (fields.get(start), fields.get(start + 1)) {
+            (Some(left), Some(right)) if start + 1 < self.widths.len() => format!(
+                "{}: {}",
+                format_to_length(left, self.widths[start]),
+                format_to_length(right, self.widths[start + 1])
+            ),
+            (Some(single), _) => format_to_length(single, self.widths[start]),
+            _ => String::new(),
+        };
+        format!("|{}|", pad_to_width(&text, inner))
+    }
+}
+
+/// Left-aligns `s` in `width` columns.
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let s = truncate_to_width(s, width);
+    let padding = width.saturating_sub(display_width(&s));
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padding_counts_columns_not_bytes() {
+        assert_eq!(format_to_length("Zoë", 5), " Zoë ");
+        assert_eq!(format_to_length("🚪", 4), " 🚪 ");
+        assert_eq!(display_width(&format_to_length("Влад", 6)), 6);
+        assert_eq!(truncate_to_width("Alexander", 5), "Alex…");
+        assert_eq!(truncate_to_width("日本語", 4), "日…");
+    }
+
+    #[test]
+    fn test_cell_layout_fits_available_columns() {
+        let layout = CellLayout::new(vec![3, 10, 5, 9, 8]);
+        assert_eq!(layout.lines(), 3);
+        assert_eq!(layout.inner_width(), 16);
+
+        let fitted = layout.fit(4, 4, 60);
+        assert_eq!(fitted.inner_width(), 56 / 4 - 2);
+        let fields: Vec<String> = ["12", "Alexander", "Alive", "Police", "100.00"]
+            .iter()
+            .map(|field| field.to_string())
+            .collect();
+        for line in 0..fitted.lines() {
+            let rendered = fitted.render_line(line, &fields);
+            assert_eq!(display_width(&rendered), fitted.inner_width() + 2);
+        }
     }
 }
@@ -0,0 +1,453 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{
+    bulletin_board::NoticeKind,
+    command,
+    console::{self, Channel, RawMode},
+    game_flow::{GameFlow, GameTime},
+    hotel::Hotel,
+    manager_states::{
+        handling_result::HandlingResult, manager_state_behavior::ManagerStateBehavior, PlayingState,
+    },
+    resident::ResidentType,
+    text_formatters::pad_to_width,
+};
+
+const CELL_WIDTH: usize = 12;
+const LOG_HEIGHT: usize = 8;
+#[derive(Default)]
+pub struct Screen {
+    pub map: Vec<String>,
+    pub panels: Vec<String>,
+    pub log: String,
+    pub input: Option<String>, // the line being typed, if any
+    pub status: String,
+    pub cursor: usize,
+}
+
+impl Screen {
+    fn push_output(&mut self, text: &str) {
+        self.log.push_str(text);
+    }
+
+    fn log_lines(&self) -> Vec<&str> {
+        let lines: Vec<&str> = self.log.lines().collect();
+        lines[lines.len().saturating_sub(LOG_HEIGHT)..].to_vec()
+    }
+
+    fn draw(&self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        queue!(stdout, terminal::Clear(ClearType::All))?;
+
+        let map_width = self.map.iter().map(|line| line.chars().count()).max();
+        let panel_column = map_width.unwrap_or(0) + 2;
+        let body_height = height.saturating_sub(LOG_HEIGHT + 3);
+        for (row, line) in self.map.iter().take(body_height).enumerate() {
+            queue!(stdout, cursor::MoveTo(0, row as u16), Print(line))?;
+        }
+        for (row, line) in self.panels.iter().take(body_height).enumerate() {
+            let line: String = line
+                .chars()
+                .take(width.saturating_sub(panel_column))
+                .collect();
+            queue!(
+                stdout,
+                cursor::MoveTo(panel_column as u16, row as u16),
+                Print(line)
+            )?;
+        }
+
+        let log_top = height.saturating_sub(LOG_HEIGHT + 2);
+        queue!(
+            stdout,
+            cursor::MoveTo(0, log_top as u16),
+            SetAttribute(Attribute::Reverse),
+            Print(format!("{:<width$}", self.status, width = width)),
+            SetAttribute(Attribute::Reset)
+        )?;
+        for (row, line) in self.log_lines().into_iter().enumerate() {
+            let line: String = line.chars().take(width).collect();
+            queue!(
+                stdout,
+                cursor::MoveTo(0, (log_top + 1 + row) as u16),
+                Print(line)
+            )?;
+        }
+        let prompt = match &self.input {
+            Some(input) => format!("> {}", input),
+            None => tr!("tui.hint"),
+        };
+        queue!(
+            stdout,
+            cursor::MoveTo(0, height.saturating_sub(1) as u16),
+            Print(prompt)
+        )?;
+        stdout.flush()
+    }
+
+    /// Edits a line at the bottom of the screen until Enter (or Esc, which gives an empty line).
+    fn read_line(&mut self) -> io::Result<String> {
+        self.input = Some(String::new());
+        loop {
+            self.draw()?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                let input = self.input.as_mut().unwrap();
+                match key.code {
+                    KeyCode::Enter => break,
+                    KeyCode::Esc => {
+                        input.clear();
+                        break;
+                    }
+                    KeyCode::Backspace => {
+                        input.pop();
+                    }
+                    KeyCode::Char(c) => input.push(c),
+                    _ => {}
+                }
+            }
+        }
+        let line = self.input.take().unwrap_or_default();
+        self.push_output(&format!("> {}\n", line));
+        Ok(line)
+    }
+}
+
+struct ScreenChannel {
+    screen: Rc<RefCell<Screen>>,
+}
+
+impl Channel for ScreenChannel {
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        let mut screen = self.screen.borrow_mut();
+        screen.push_output(text);
+        screen.draw()
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let line = self.screen.borrow_mut().read_line()?;
+        Ok(Some(format!("{}\n", line)))
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        let mut screen = self.screen.borrow_mut();
+        screen.log.clear();
+        screen.draw()
+    }
+}
+
+pub fn move_cursor(cursor: usize, key: KeyCode, num_rooms: usize, rooms_per_story: usize) -> usize {
+    match key {
+        KeyCode::Left => cursor.saturating_sub(1),
+        KeyCode::Right if cursor + 1 < num_rooms => cursor + 1,
+        KeyCode::Up if cursor + rooms_per_story < num_rooms => cursor + rooms_per_story,
+        KeyCode::Down if cursor >= rooms_per_story => cursor - rooms_per_story,
+        _ => cursor,
+    }
+}
+
+/// The hotel as a grid of cells: `>` `<` mark the cursor, `*` the apartments that may be chosen
+/// and `@` the apartment of the player at the keyboard.
+pub fn map_lines(
+    hotel: &Hotel,
+    cursor: usize,
+    selectable: &[usize],
+    viewer: Option<usize>,
+) -> Vec<String> {
+    let mut lines = vec![];
+    for floor in hotel.floor_plan() {
+        let mut line = String::new();
+        for (room, idx) in floor.into_iter().enumerate() {
+            if room == hotel.elevator_position {
+                line.push_str("|^v|");
+            }
+            let Some(idx) = idx else {
+                line.push_str(&format!("{: ^width$}", "", width = CELL_WIDTH));
+                continue;
+            };
+            let apartment = &hotel.apartments[idx];
+            let name = match &apartment.resident {
+                Some(_) if hotel.bulletin_board.has_notice(NoticeKind::Death, idx) => {
+                    tr!("tui.dead")
+                }
+                Some(resident) => resident.lock().unwrap().name.clone(),
+                None => tr!("tui.vacant"),
+            };
+            let mark = if Some(idx) == viewer {
+                '@'
+            } else if selectable.contains(&idx) {
+                '*'
+            } else {
+                ' '
+            };
+            let (left, right) = if idx == cursor {
+                ('>', '<')
+            } else {
+                ('[', ']')
+            };
+            let name = pad_to_width(&name, CELL_WIDTH - 5);
+            line.push_str(&format!("{}{:02}{}{}{}", left, idx, mark, name, right));
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Documents, inbox and last night's events of the human whose turn it is.
+pub fn panel_lines(game_flow: &GameFlow) -> Vec<String> {
+    let mut lines = vec![];
+    let viewer = game_flow
+        .residents
+        .get(game_flow.current_moving_player)
+        .filter(|resident| resident.lock().unwrap().resident_type == ResidentType::Human);
+    match viewer {
+        Some(resident) => {
+            let resident = resident.lock().unwrap();
+            let apartment = &game_flow.hotel.apartments[resident.apartment_number];
+            lines.push(tr!(
+                "tui.resident",
+                resident.name,
+                resident.apartment_number
+            ));
+            lines.push(tr!(
+                "tui.role",
+                resident.strategy.confess_role(),
+                format!("{:.2}", resident.account_balance)
+            ));
+            lines.push(String::new());
+            lines.push(tr!("tui.documents"));
+            for document in &resident.documents {
+                lines.push(format!(
+                    "{} ({}), {}",
+                    document.name, document.role, document.title
+                ));
+            }
+            lines.push(String::new());
+            lines.push(tr!("tui.inbox", apartment.unread_mails_count()));
+            for (i, mail) in apartment.mails.iter().enumerate() {
+                lines.push(format!("{}. {}", i + 1, mail));
+            }
+        }
+        None => lines.push(tr!("tui.bots_moving")),
+    }
+
+    let last_night = match game_flow.current_state {
+        GameTime::Day => game_flow.game_history.day.saturating_sub(1),
+        GameTime::Night => game_flow.game_history.day,
+    };
+    lines.push(String::new());
+    lines.push(tr!("tui.night", last_night));
+    let history = &game_flow.game_history;
+    let events = match game_flow.viewer() {
+        None => history.retell_day(&game_flow.hotel, Some("n (#)"), last_night),
+        Some(viewer) => viewer
+            .apartment
+            .map(|own| history.retell_own_day(&game_flow.hotel, Some("n (#)"), last_night, own))
+            .unwrap_or_default(),
+    };
+    lines.extend(events.lines().map(|line| line.to_string()));
+    lines
+}
+
+pub struct Tui {
+    screen: Rc<RefCell<Screen>>,
+}
+
+/// Gives the terminal and the console back when dropped, even if a command panics mid-game.
+struct Takeover {
+    previous_host: Option<Box<dyn Channel>>,
+    _raw_mode: RawMode, // dropped last, once the screen is restored
+}
+
+impl Drop for Takeover {
+    fn drop(&mut self) {
+        console::set_target_picker(None);
+        console::set_host(self.previous_host.take());
+        execute!(io::stdout(), cursor::Show, LeaveAlternateScreen).ok();
+    }
+}
+
+impl Tui {
+    pub fn new() -> Self {
+        Self {
+            screen: Rc::new(RefCell::new(Screen::default())),
+        }
+    }
+
+    /// Takes over the terminal until the player presses `q` or a command changes the state.
+    pub fn run(&self, game_flow: &mut GameFlow) -> io::Result<HandlingResult> {
+        let raw_mode = RawMode::enable()?;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        let _takeover = Takeover {
+            previous_host: console::set_host(Some(Box::new(ScreenChannel {
+                screen: Rc::clone(&self.screen),
+            }))),
+            _raw_mode: raw_mode,
+        };
+        let picker_screen = Rc::clone(&self.screen);
+        console::set_target_picker(Some(Box::new(move |hotel, options| {
+            Self::pick_target(&picker_screen, hotel, options)
+        })));
+
+        self.event_loop(game_flow)
+    }
+
+    fn event_loop(&self, game_flow: &mut GameFlow) -> io::Result<HandlingResult> {
+        let mut playing_state = PlayingState;
+        loop {
+            self.refresh(game_flow);
+            self.screen.borrow().draw()?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(HandlingResult::KeepState),
+                KeyCode::Char('m') | KeyCode::Enter if !game_flow.residents.is_empty() => {
+                    playing_state.handle_command(game_flow, &["move"]);
+                }
+                KeyCode::Char(':') => {
+                    let line = self.screen.borrow_mut().read_line()?;
+                    let tokens = match command::tokenize(&line) {
+                        Ok(tokens) => tokens,
+                        Err(error) => {
+                            self.screen
+                                .borrow_mut()
+                                .push_output(&format!("{}\n", error));
+                            continue;
+                        }
+                    };
+                    let input: Vec<&str> = tokens.iter().map(String::as_str).collect();
+                    match input.first() {
+                        None => {}
+                        Some(&"tui") => self
+                            .screen
+                            .borrow_mut()
+                            .push_output(&format!("{}\n", tr!("tui.already_here"))),
+                        Some(_) => match playing_state.handle_command(game_flow, &input) {
+                            HandlingResult::KeepState => {}
+                            result => return Ok(result),
+                        },
+                    }
+                }
+                code => {
+                    let mut screen = self.screen.borrow_mut();
+                    let hotel = &game_flow.hotel;
+                    screen.cursor = move_cursor(
+                        screen.cursor,
+                        code,
+                        hotel.apartments.len(),
+                        hotel.rooms_per_story,
+                    );
+                }
+            }
+        }
+    }
+
+    fn refresh(&self, game_flow: &GameFlow) {
+        let viewer = game_flow
+            .residents
+            .get(game_flow.current_moving_player)
+            .map(|resident| resident.lock().unwrap())
+            .filter(|resident| resident.resident_type == ResidentType::Human)
+            .map(|resident| resident.apartment_number);
+        let mut screen = self.screen.borrow_mut();
+        screen.map = map_lines(&game_flow.hotel, screen.cursor, &[], viewer);
+        screen.panels = panel_lines(game_flow);
+        let time = tr!(&format!("tui.{:?}", game_flow.current_state));
+        screen.status = format!(
+            " {} ",
+            tr!(
+                "tui.status",
+                game_flow.hotel.id,
+                game_flow.game_history.day,
+                time
+            )
+        );
+    }
+
+    fn pick_target(
+        screen: &Rc<RefCell<Screen>>,
+        hotel: &Hotel,
+        options: &[usize],
+    ) -> Option<usize> {
+        let mut screen = screen.borrow_mut();
+        let previous_status = std::mem::replace(
+            &mut screen.status,
+            format!(" {} ", tr!("tui.choose_target")),
+        );
+        let picked = loop {
+            screen.map = map_lines(hotel, screen.cursor, options, None);
+            if screen.draw().is_err() {
+                break None;
+            }
+            let Ok(Event::Key(key)) = event::read() else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Enter if options.contains(&screen.cursor) => break Some(screen.cursor),
+                KeyCode::Enter => screen.push_output(&format!("{}\n", tr!("tui.cannot_choose"))),
+                KeyCode::Esc => break None,
+                code => {
+                    screen.cursor = move_cursor(
+                        screen.cursor,
+                        code,
+                        hotel.apartments.len(),
+                        hotel.rooms_per_story,
+                    )
+                }
+            }
+        };
+        screen.status = previous_status;
+        picked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_hotel() -> Hotel {
+        Hotel::for_test(5, 3, &[])
+    }
+
+    #[test]
+    fn test_move_cursor_stays_inside_the_hotel() {
+        assert_eq!(move_cursor(0, KeyCode::Left, 5, 3), 0);
+        assert_eq!(move_cursor(0, KeyCode::Up, 5, 3), 3);
+        assert_eq!(move_cursor(2, KeyCode::Up, 5, 3), 2); // there is no apartment 5
+        assert_eq!(move_cursor(4, KeyCode::Down, 5, 3), 1);
+        assert_eq!(move_cursor(4, KeyCode::Right, 5, 3), 4);
+    }
+
+    #[test]
+    fn test_map_lines_mark_cursor_and_targets() {
+        let hotel = small_hotel();
+        let lines = map_lines(&hotel, 4, &[0, 2], Some(1));
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(">04 vacant <"));
+        assert!(lines[1].contains("[00*vacant ]"));
+        assert!(lines[1].contains("[01@vacant ]"));
+        assert!(lines[1].contains("|^v|"));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::command::{ArgKind, Value};
+use crate::hotel::{BuildingType, Hotel};
+
+/// A setting chosen while setting the hotel up, in the order the wizard asks for them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Setting {
+    Rooms,
+    RoomsPerStory,
+    Elevator,
+    Building,
+    Capital,
+    Fee,
+    Service,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 7] = [
+        Setting::Rooms,
+        Setting::RoomsPerStory,
+        Setting::Elevator,
+        Setting::Building,
+        Setting::Capital,
+        Setting::Fee,
+        Setting::Service,
+    ];
+
+    /// The setup command that changes the setting, also the key of its texts.
+    pub fn command(self) -> &'static str {
+        match self {
+            Setting::Rooms => "rooms",
+            Setting::RoomsPerStory => "rps",
+            Setting::Elevator => "elevator",
+            Setting::Building => "building",
+            Setting::Capital => "capital",
+            Setting::Fee => "fee",
+            Setting::Service => "service",
+        }
+    }
+
+    pub fn from_command(command: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|setting| setting.command() == command)
+    }
+
+    pub fn kind(self) -> ArgKind {
+        match self {
+            Setting::Rooms | Setting::RoomsPerStory => ArgKind::Positive,
+            Setting::Elevator => ArgKind::Index,
+            Setting::Building => ArgKind::Choice(&BuildingType::NAMES),
+            Setting::Capital | Setting::Fee | Setting::Service => ArgKind::Number,
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("setting.{}", self.command()))
+    }
+
+    pub fn current(self, hotel: &Hotel) -> String {
+        match self {
+            Setting::Rooms => hotel.num_rooms.to_string(),
+            Setting::RoomsPerStory => hotel.rooms_per_story.to_string(),
+            Setting::Elevator => hotel.elevator_position.to_string(),
+            Setting::Building => hotel.building_type.name().to_string(),
+            Setting::Capital => hotel.capital.to_string(),
+            Setting::Fee => hotel.entrance_fee.to_string(),
+            Setting::Service => hotel.daily_costs.to_string(),
+        }
+    }
+
+    fn assign(self, hotel: &mut Hotel, value: &Value) {
+        match (self, value) {
+            (Setting::Rooms, Value::Integer(rooms)) => hotel.num_rooms = *rooms,
+            (Setting::RoomsPerStory, Value::Integer(rooms)) => hotel.rooms_per_story = *rooms,
+            (Setting::Elevator, Value::Integer(position)) => hotel.elevator_position = *position,
+            (Setting::Building, Value::Text(name)) => {
+                hotel.building_type = BuildingType::parse(name).unwrap()
+            }
+            (Setting::Capital, Value::Number(capital)) => hotel.capital = *capital,
+            (Setting::Fee, Value::Number(fee)) => hotel.entrance_fee = *fee,
+            (Setting::Service, Value::Number(costs)) => hotel.daily_costs = *costs,
+            _ => unreachable!("the value was parsed with the kind of the setting"),
+        }
+    }
+}
+
+/// A rule the settings break, and the settings it is about.
+pub struct Problem {
+    pub settings: Vec<Setting>,
+    pub message: String,
+}
+
+/// Everything wrong with the settings of `hotel`.
+pub fn problems(hotel: &Hotel) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut rule = |broken: bool, settings: &[Setting], message: String| {
+        if broken {
+            problems.push(Problem {
+                settings: settings.to_vec(),
+                message,
+            });
+        }
+    };
+    for (setting, empty) in [
+        (Setting::Rooms, hotel.num_rooms == 0),
+        (Setting::RoomsPerStory, hotel.rooms_per_story == 0),
+        (Setting::Capital, hotel.capital <= 0.0),
+        (Setting::Fee, hotel.entrance_fee <= 0.0),
+        (Setting::Service, hotel.daily_costs <= 0.0),
+    ] {
+        rule(
+            empty,
+            &[setting],
+            tr!("setup.not_positive", setting.label()),
+        );
+    }
+    rule(
+        hotel.elevator_position > hotel.rooms_per_story,
+        &[Setting::Elevator, Setting::RoomsPerStory],
+        tr!(
+            "setup.elevator_outside",
+            hotel.elevator_position,
+            hotel.rooms_per_story
+        ),
+    );
+    problems
+}
+
+/// Sets `setting` unless the value is wrong on its own. What it leaves wrong together
+/// with other settings comes back to be fixed before the setup is finished.
+pub fn set(hotel: &mut Hotel, setting: Setting, value: &Value) -> Result<Vec<String>, String> {
+    let mut changed = hotel.clone();
+    setting.assign(&mut changed, value);
+    let problems: Vec<Problem> = problems(&changed)
+        .into_iter()
+        .filter(|problem| problem.settings.contains(&setting))
+        .collect();
+    if let Some(problem) = problems
+        .iter()
+        .find(|problem| problem.settings == [setting])
+    {
+        return Err(problem.message.clone());
+    }
+    setting.assign(hotel, value);
+    Ok(problems
+        .into_iter()
+        .map(|problem| problem.message)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_are_checked_alone_and_together() {
+        let mut hotel = Hotel::for_test(16, 4, &[]);
+        assert!(problems(&hotel).is_empty());
+
+        assert!(set(&mut hotel, Setting::Capital, &Value::Number(0.0)).is_err());
+        assert_eq!(hotel.capital, 1000.0);
+
+        let warnings = set(&mut hotel, Setting::Elevator, &Value::Integer(6)).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(hotel.elevator_position, 6);
+        assert_eq!(
+            problems(&hotel)[0].settings,
+            [Setting::Elevator, Setting::RoomsPerStory]
+        );
+        set(&mut hotel, Setting::RoomsPerStory, &Value::Integer(8)).unwrap();
+        assert!(problems(&hotel).is_empty());
+
+        let building = Value::Text("pyramidal".to_string());
+        set(&mut hotel, Setting::Building, &building).unwrap();
+        assert_eq!(Setting::Building.current(&hotel), "pyramidal");
+    }
+}